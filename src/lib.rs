@@ -3,6 +3,12 @@
 //! This crate serves as a reference implementation of the algorithm described in Kate Morley’s
 //! article [Vertical label placement](https://iamkate.com/code/vertical-label-placement/).
 //!
+//! For sorted preferred positions and a non-negative minimum separation, the permitted positions
+//! are always sorted in ascending order and separated by at least the minimum separation, provided
+//! they can be represented as `i32` values. With limits that provide sufficient space, they also lie
+//! within the limits. These guarantees are tested in every build profile rather than enforced by
+//! debug assertions.
+//!
 //! # Examples
 //!
 //! Placing labels, respecting a minimum separation:
@@ -34,7 +40,12 @@
 //! # }
 //! ```
 
-use std::cmp::{max, min};
+use std::borrow::Cow;
+use std::cmp::{max, min, Ordering, Reverse};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
 
 /// Places labels, respecting a minimum separation.
 ///
@@ -50,19 +61,11 @@ use std::cmp::{max, min};
 /// # }
 /// ```
 pub fn place(positions: &[i32], separation: i32) -> Vec<i32> {
-    let mut clusters = ClusterList::new(separation, positions.len());
-
-    for position in positions {
-        let mut cluster = Cluster::new(*position);
-
-        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
-            cluster = Cluster::merge(previous, cluster, separation);
-        }
-
-        clusters.push(cluster);
+    if are_separated(positions, separation) {
+        return positions.to_vec();
     }
 
-    clusters.positions()
+    cluster(positions, separation).positions()
 }
 
 /// Places labels, respecting a minimum separation and minimum and maximum positions.
@@ -125,183 +128,7015 @@ pub fn place_with_limits(positions: &[i32], separation: i32, min: i32, max: i32)
     clusters.positions()
 }
 
-/// Represents a set of neighbouring labels whose permitted positions are separated by exactly the
-/// minimum separation.
-#[derive(Copy, Clone)]
-struct Cluster {
-    /// The start position.
-    start: i32,
-    /// The end position.
-    end: i32,
-    /// The minimum offset.
-    min_offset: i32,
-    /// The maximum offset.
-    max_offset: i32,
-}
+/// Places labels, respecting a minimum separation that is reduced, if necessary, so that the
+/// permitted positions span no more than a maximum extent.
+///
+/// The largest separation no greater than the preferred separation is used. If the preferred
+/// positions themselves span more than the maximum extent, a separation of zero is used and the
+/// labels are left at their preferred positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_within_extent(&preferred_positions, 10, 10);
+///
+/// assert_eq!([-5, 0, 5], *permitted_positions);
+/// # }
+/// ```
+pub fn place_within_extent(
+    positions: &[i32],
+    preferred_separation: i32,
+    max_extent: i32,
+) -> Vec<i32> {
+    let extent = |placed: &[i32]| match (placed.first(), placed.last()) {
+        (Some(first), Some(last)) => last - first,
+        _ => 0,
+    };
 
-impl Cluster {
-    /// Creates a new cluster containing a single position.
-    fn new(position: i32) -> Self {
-        Self {
-            start: position,
-            end: position,
-            min_offset: 0,
-            max_offset: 0,
-        }
-    }
+    let mut low = 0;
+    let mut high = max(preferred_separation, 0);
 
-    /// Creates a new cluster by merging two neighbouring clusters.
-    fn merge(mut first: Self, second: Self, separation: i32) -> Self {
-        first.shift(second.start - first.end - separation);
+    while low < high {
+        let separation = low + (high - low + 1) / 2;
 
-        Self {
-            start: first.start,
-            end: second.end,
-            min_offset: min(first.min_offset, second.min_offset),
-            max_offset: max(first.max_offset, second.max_offset),
+        if extent(&place(positions, separation)) <= max_extent {
+            low = separation;
+        } else {
+            high = separation - 1;
         }
-        .balance()
     }
 
-    /// Moves the cluster by an offset.
-    fn shift(&mut self, offset: i32) {
-        self.start += offset;
-        self.end += offset;
-        self.min_offset += offset;
-        self.max_offset += offset;
-    }
+    place(positions, low)
+}
 
-    /// Shifts the cluster to minimise the sum of `min_offset` and `max_offset`.
-    ///
-    /// This is equivalent to minimising the maximum absolute offset within the cluster.
-    fn balance(mut self) -> Self {
-        let imbalance = (self.min_offset + self.max_offset) / 2;
+/// Places labels, respecting a minimum separation, by overwriting the preferred positions with the
+/// permitted positions.
+///
+/// The preferred positions must be sorted in ascending order, so that the labels in each cluster
+/// are contiguous within the slice.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let mut positions = vec![-10, -1, 1, 10];
+///
+/// vertical_label_placement::place_in_place(&mut positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15], *positions);
+/// # }
+/// ```
+pub fn place_in_place(positions: &mut [i32], separation: i32) {
+    cluster(positions, separation).write_positions(positions);
+}
 
-        if imbalance != 0 {
-            self.shift(-imbalance);
-        }
+/// Places labels on ticks, respecting a minimum separation.
+///
+/// Having placed the labels as `place()` would, each label in turn is moved to the nearest tick
+/// that is at least the minimum separation from the previous label. The ticks must be sorted in
+/// ascending order. If there are insufficient ticks, the remaining labels are placed at exactly
+/// the minimum separation from the previous label.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![52, 52];
+/// let ticks = vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_on_ticks(&preferred_positions, 10, &ticks);
+///
+/// assert_eq!([50, 60], *permitted_positions);
+/// # }
+/// ```
+pub fn place_on_ticks(positions: &[i32], separation: i32, ticks: &[i32]) -> Vec<i32> {
+    let mut placed = place(positions, separation);
+    let mut available = ticks;
+    let mut previous: Option<i32> = None;
 
-        self
-    }
+    for position in &mut placed {
+        if let Some(previous) = previous {
+            let lowest = previous + separation;
+            available = &available[available.partition_point(|&tick| tick < lowest)..];
 
-    /// Shifts the cluster to respect the limits.
-    fn limit(mut self, min: i32, max: i32) -> Self {
-        if self.start < min {
-            self.shift(min - self.start);
+            if available.is_empty() {
+                *position = lowest;
+            }
         }
 
-        if self.end > max {
-            self.shift(max - self.end);
+        if !available.is_empty() {
+            let index = available.partition_point(|&tick| tick < *position);
+
+            let nearest = if index == available.len()
+                || (index > 0 && *position - available[index - 1] <= available[index] - *position)
+            {
+                index - 1
+            } else {
+                index
+            };
+
+            *position = available[nearest];
+            available = &available[nearest + 1..];
         }
 
-        self
+        previous = Some(*position);
     }
+
+    placed
 }
 
-/// Represents a list of clusters, providing stack-like access.
-struct ClusterList {
-    /// The vector of clusters.
-    vec: Vec<Cluster>,
-    /// The minimum separation.
+/// Places labels, respecting a minimum separation, returning the start position and number of
+/// labels of each cluster.
+///
+/// Within each cluster, the labels are placed at exactly the minimum separation, so the permitted
+/// positions are `start`, `start + separation`, `start + 2 * separation`, and so on.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let clusters = vertical_label_placement::place_rle(&preferred_positions, 10);
+///
+/// assert_eq!([(-15, 4), (100, 1)], *clusters);
+/// # }
+/// ```
+pub fn place_rle(positions: &[i32], separation: i32) -> Vec<(i32, usize)> {
+    cluster(positions, separation).runs()
+}
+
+/// Places labels, respecting a minimum separation, returning the permitted position of each label
+/// whose position differs from a previous placement, and `None` for each label whose position is
+/// unchanged.
+///
+/// Labels beyond the end of the previous placement are treated as changed.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let previous_positions = vertical_label_placement::place(&[-10, -1, 1, 10], 10);
+///
+/// let changes = vertical_label_placement::place_diff(&[-10, -1, 1, 30], 10, &previous_positions);
+///
+/// assert_eq!([None, None, None, Some(30)], *changes);
+/// # }
+/// ```
+pub fn place_diff(positions: &[i32], separation: i32, previous: &[i32]) -> Vec<Option<i32>> {
+    place(positions, separation)
+        .into_iter()
+        .enumerate()
+        .map(|(index, position)| (previous.get(index) != Some(&position)).then_some(position))
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation and exclusive minimum and maximum positions.
+///
+/// As positions are integers, this is equivalent to calling `place_with_limits()` with limits of
+/// `min + 1` and `max - 1`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_with_exclusive_limits(
+///     &preferred_positions,
+///     10,
+///     0,
+///     100
+/// );
+///
+/// assert_eq!([1, 11, 21, 31], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_exclusive_limits(
+    positions: &[i32],
     separation: i32,
-    /// The requested capacity.
-    capacity: usize,
+    min: i32,
+    max: i32,
+) -> Vec<i32> {
+    place_with_limits(
+        positions,
+        separation,
+        min.saturating_add(1),
+        max.saturating_sub(1),
+    )
 }
 
-impl ClusterList {
-    /// Creates a new list of clusters.
-    ///
-    /// Providing a capacity equal to the number of labels prevents reallocation of vectors in
-    /// `push()` and `positions()`.
-    fn new(separation: i32, capacity: usize) -> Self {
-        Self {
-            vec: Vec::with_capacity(capacity),
-            separation,
-            capacity,
-        }
-    }
+/// Places labels, respecting a minimum separation, preferring to keep earlier labels closer to
+/// their preferred positions.
+///
+/// When a cluster can be placed in two positions that are equally good, `place()` chooses the
+/// position requiring the smaller shift of the cluster, whereas this function chooses the position
+/// that minimises the offset of the first label in the cluster.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0];
+///
+/// let permitted_positions = vertical_label_placement::place_stable(&preferred_positions, 5);
+///
+/// assert_eq!([-2, 3], *permitted_positions);
+/// # }
+/// ```
+pub fn place_stable(positions: &[i32], separation: i32) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
 
-    /// Pops and returns the last cluster from the list if it is not sufficiently separated from the
-    /// specified cluster, and otherwise returns `None`.
-    fn pop_if_not_separate(&mut self, cluster: Cluster) -> Option<Cluster> {
-        if let Some(previous) = self.vec.last() {
-            if previous.end + self.separation > cluster.start {
-                return self.vec.pop();
-            }
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation).balance_stable();
         }
 
-        None
+        clusters.push(cluster);
     }
 
-    /// Pushes a cluster onto the end of the list.
-    fn push(&mut self, cluster: Cluster) {
-        self.vec.push(cluster);
-    }
+    clusters.positions()
+}
 
-    /// Transforms the list into a vector of permitted positions.
-    fn positions(self) -> Vec<i32> {
-        let mut positions = Vec::with_capacity(self.capacity);
+/// Places labels, respecting a minimum separation, by moving each label only as far as necessary
+/// to be separated from the previous label.
+///
+/// Unlike `place()`, this never moves labels towards lower positions, so offsets accumulate
+/// towards higher positions rather than being balanced.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_greedy(&preferred_positions, 10);
+///
+/// assert_eq!([-10, 0, 10, 20], *permitted_positions);
+/// # }
+/// ```
+pub fn place_greedy(positions: &[i32], separation: i32) -> Vec<i32> {
+    let mut placed = Vec::with_capacity(positions.len());
 
-        for cluster in self.vec {
-            let mut position = cluster.start;
-            while position <= cluster.end {
-                positions.push(position);
-                position += self.separation;
-            }
-        }
+    for position in positions {
+        let position = match placed.last() {
+            Some(previous) => max(*position, previous + separation),
+            None => *position,
+        };
 
-        positions
+        placed.push(position);
     }
+
+    placed
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Places labels, respecting a minimum separation, returning statistics about the placement
+/// alongside the permitted positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Stats;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let (permitted_positions, stats) =
+///     vertical_label_placement::place_with_stats(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15, 100], *permitted_positions);
+/// assert_eq!(
+///     Stats {
+///         merges: 3,
+///         max_cluster_size: 4,
+///         cluster_count: 2
+///     },
+///     stats
+/// );
+/// # }
+/// ```
+pub fn place_with_stats(positions: &[i32], separation: i32) -> (Vec<i32>, Stats) {
+    let mut clusters = ClusterList::new(separation, positions.len());
+    let mut stats = Stats::default();
 
-    #[test]
-    fn already_separated() {
-        assert_eq!([0], *place(&[0], 10));
-        assert_eq!([0, 10], *place(&[0, 10], 10));
-        assert_eq!([-10, 0, 10], *place(&[-10, 0, 10], 10));
-    }
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
 
-    #[test]
-    fn already_separated_but_outside_limits() {
-        assert_eq!([-10], *place_with_limits(&[-20], 10, -10, 10));
-        assert_eq!([10], *place_with_limits(&[20], 10, -10, 10));
-        assert_eq!([-10, 10], *place_with_limits(&[-20, 20], 10, -10, 10));
-    }
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation);
+            stats.merges += 1;
+        }
 
-    #[test]
-    fn overflowing_limits() {
-        assert_eq!([-20, -10, 0], *place_with_limits(&[0, 0, 0], 10, 0, 0));
+        stats.max_cluster_size = max(stats.max_cluster_size, cluster.len);
+        clusters.push(cluster);
     }
 
-    #[test]
-    fn one_cluster() {
-        assert_eq!([-5, 5], *place(&[0, 0], 10));
-        assert_eq!([-10, 0, 10], *place(&[0, 0, 0], 10));
-        assert_eq!([-20, -10, 0], *place_with_limits(&[0, 0, 0], 10, -20, 0));
-        assert_eq!([0, 10, 20], *place_with_limits(&[0, 0, 0], 10, 0, 20));
-    }
+    stats.cluster_count = clusters.vec.len();
 
-    #[test]
-    fn two_clusters() {
-        assert_eq!(
-            [-30, -20, -10, 10, 20, 30],
+    (clusters.positions(), stats)
+}
+
+/// Places labels with heights, respecting a minimum gap between neighbouring labels and minimum
+/// and maximum positions for the edges of the labels.
+///
+/// Each label extends from `center - height / 2` to `center + (height - height / 2)`, so labels
+/// are separated by their half-heights plus the gap, and the edges of each label are kept between
+/// `floor` and `ceiling`. As with `place_with_limits()`, if the limits do not provide sufficient
+/// space for all the labels, only the ceiling will be respected.
+///
+/// A label with a height of zero, such as a marker, occupies no space, so it requires no
+/// separation beyond the gap and the half-heights of its neighbours. Labels either side of it are
+/// still separated by their half-heights plus twice the gap.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let centers = vec![0, 50, 95];
+/// let heights = vec![10, 10, 30];
+///
+/// let permitted_centers =
+///     vertical_label_placement::place_sized_with_limits(&centers, &heights, 0, -100, 100);
+///
+/// assert_eq!([0, 50, 85], *permitted_centers);
+/// # }
+/// ```
+pub fn place_sized_with_limits(
+    centers: &[i32],
+    heights: &[i32],
+    gap: i32,
+    floor: i32,
+    ceiling: i32,
+) -> Vec<i32> {
+    place_with_separations(
+        centers,
+        |index| heights[index - 1] - heights[index - 1] / 2 + heights[index] / 2 + gap,
+        |index| {
+            (
+                floor + heights[index] / 2,
+                ceiling - (heights[index] - heights[index] / 2),
+            )
+        },
+    )
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, with the
+/// specified behaviour if the limits do not provide sufficient space for all the labels.
+///
+/// # Panics
+///
+/// Panics if `overflow` is `Overflow::Compress` and `min` is greater than `max`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Overflow;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_with_overflow(
+///     &preferred_positions,
+///     10,
+///     -10,
+///     10,
+///     Overflow::Centre
+/// );
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_overflow(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    overflow: Overflow,
+) -> Vec<i32> {
+    assert!(
+        overflow != Overflow::Compress || min <= max,
+        "minimum is greater than maximum"
+    );
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position).limit_with_overflow(min, max, overflow);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation)
+                .limit_with_overflow(min, max, overflow);
+        }
+
+        clusters.push(cluster);
+    }
+
+    let mut positions = clusters.positions();
+
+    match overflow {
+        Overflow::Clamp => {
+            for position in &mut positions {
+                *position = (*position).clamp(min, max);
+            }
+        }
+        Overflow::Compress
+            if i64::from(separation) * (positions.len() as i64 - 1)
+                > i64::from(max) - i64::from(min) =>
+        {
+            let range = i64::from(max) - i64::from(min);
+            let steps = positions.len() as i64 - 1;
+
+            for (index, position) in positions.iter_mut().enumerate() {
+                *position = (i64::from(min) + range * index as i64 / steps) as i32;
+            }
+        }
+        _ => {}
+    }
+
+    positions
+}
+
+/// Places labels, respecting a minimum separation, collecting the permitted positions into the
+/// specified type of collection.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use std::collections::VecDeque;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions: VecDeque<i32> =
+///     vertical_label_placement::place_collect(&preferred_positions, 10);
+///
+/// assert_eq!(VecDeque::from([-15, -5, 5, 15]), permitted_positions);
+/// # }
+/// ```
+pub fn place_collect<C: FromIterator<i32>>(positions: &[i32], separation: i32) -> C {
+    cluster(positions, separation).into_positions().collect()
+}
+
+/// Places labels, respecting a minimum separation, returning the index and offset of the label
+/// with the maximum absolute offset from its preferred position, or `None` if there are no labels.
+///
+/// If several labels have the maximum absolute offset, the first of them is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 12];
+///
+/// let worst_label = vertical_label_placement::worst_label(&preferred_positions, 10);
+///
+/// assert_eq!(Some((0, -5)), worst_label);
+/// # }
+/// ```
+pub fn worst_label(positions: &[i32], separation: i32) -> Option<(usize, i32)> {
+    let mut worst: Option<(usize, i32)> = None;
+
+    for (index, (placed, preferred)) in place(positions, separation)
+        .into_iter()
+        .zip(positions)
+        .enumerate()
+    {
+        let offset = placed - preferred;
+
+        if worst.is_none_or(|(_, worst_offset)| offset.abs() > worst_offset.abs()) {
+            worst = Some((index, offset));
+        }
+    }
+
+    worst
+}
+
+/// Places labels with floating-point positions, respecting a minimum separation, and rounds the
+/// permitted positions to integers.
+///
+/// As integer positions can be no closer than the minimum separation rounded up to an integer, the
+/// labels are placed in floating point at that separation. The start of each cluster is then
+/// rounded using the specified rounding, and the labels within the cluster follow it at that
+/// separation, so every label in a cluster is rounded the same way. Rounding the starts of
+/// separated clusters in the same direction keeps them separated, except when rounding halfway
+/// cases to even moves one cluster down and the next up, in which case the halfway case of the
+/// later cluster is rounded up instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Rounding;
+///
+/// let preferred_positions = vec![7.5, 12.5];
+///
+/// let permitted_positions = vertical_label_placement::place_f64_rounded(
+///     &preferred_positions,
+///     5.0,
+///     Rounding::HalfEven
+/// );
+///
+/// assert_eq!([8, 13], *permitted_positions);
+/// # }
+/// ```
+pub fn place_f64_rounded(positions: &[f64], separation: f64, rounding: Rounding) -> Vec<i32> {
+    let step = separation.ceil() as i32;
+    let mut rounded: Vec<i32> = Vec::with_capacity(positions.len());
+
+    for (start, len) in cluster_f64(positions, f64::from(step)) {
+        let start = match rounded.last() {
+            Some(previous) => max(rounding.round(start), previous + step),
+            None => rounding.round(start),
+        };
+
+        rounded.extend((0..len).map(|index| start + step * index as i32));
+    }
+
+    rounded
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, keeping the
+/// required labels within the limits even if other labels must be placed beyond them.
+///
+/// If a cluster does not fit within the limits but contains required labels, the cluster is kept
+/// as close as possible to the limits while keeping the required labels within them. If the
+/// required labels themselves do not fit, only the maximum limit will be respected for them.
+///
+/// # Panics
+///
+/// Panics if `required` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0];
+///
+/// let permitted_positions = vertical_label_placement::place_with_required(
+///     &preferred_positions,
+///     10,
+///     0,
+///     10,
+///     &[true, false, false]
+/// );
+///
+/// assert_eq!([0, 10, 20], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_required(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    required: &[bool],
+) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        required.len(),
+        "required count does not match"
+    );
+
+    let limit = |cluster: Cluster, required: Option<(usize, usize)>| match required {
+        Some((first, last)) if cluster.end - cluster.start > max - min => cluster.limit(
+            min - separation * first as i32,
+            max + separation * (cluster.len - 1 - last) as i32,
+        ),
+        _ => cluster.limit(min, max),
+    };
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+    let mut cluster_required: Vec<Option<(usize, usize)>> = Vec::with_capacity(positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut range = required[index].then_some((0, 0));
+        let mut cluster = limit(Cluster::new(*position), range);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            let previous_range = cluster_required.pop().flatten();
+
+            range = match (previous_range, range) {
+                (Some((first, last)), None) => Some((first, last)),
+                (Some((first, _)), Some((_, last))) => Some((first, previous.len + last)),
+                (None, Some((first, last))) => Some((previous.len + first, previous.len + last)),
+                (None, None) => None,
+            };
+
+            cluster = limit(Cluster::merge(previous, cluster, separation), range);
+        }
+
+        clusters.push(cluster);
+        cluster_required.push(range);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, folding the index and permitted position of
+/// each label into an accumulator without collecting the permitted positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let max_offset = vertical_label_placement::place_fold(
+///     &preferred_positions,
+///     10,
+///     0,
+///     |max_offset, index, position| max_offset.max((position - preferred_positions[index]).abs())
+/// );
+///
+/// assert_eq!(5, max_offset);
+/// # }
+/// ```
+pub fn place_fold<B>(
+    positions: &[i32],
+    separation: i32,
+    init: B,
+    mut f: impl FnMut(B, usize, i32) -> B,
+) -> B {
+    cluster(positions, separation)
+        .into_positions()
+        .enumerate()
+        .fold(init, |accumulator, (index, position)| {
+            f(accumulator, index, position)
+        })
+}
+
+/// Places labels, respecting a minimum separation of `base_separation * scale_num / scale_den`.
+///
+/// The separation is computed exactly using 64-bit integer arithmetic and then rounded towards
+/// zero, saturating at the bounds of `i32`.
+///
+/// # Panics
+///
+/// Panics if `scale_den` is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_scaled_separation(&preferred_positions, 20, 1, 2);
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_scaled_separation(
+    positions: &[i32],
+    base_separation: i32,
+    scale_num: i32,
+    scale_den: i32,
+) -> Vec<i32> {
+    let separation = i64::from(base_separation) * i64::from(scale_num) / i64::from(scale_den);
+
+    place(
+        positions,
+        separation.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+    )
+}
+
+/// Places labels, respecting a minimum separation, returning only the permitted position of the
+/// label at the specified index.
+///
+/// All the labels must still be grouped into clusters, as later labels may cause earlier clusters
+/// to be merged, but only the requested position is computed from the clusters.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_position = vertical_label_placement::place_single(&preferred_positions, 10, 2);
+///
+/// assert_eq!(5, permitted_position);
+/// # }
+/// ```
+pub fn place_single(positions: &[i32], separation: i32, index: usize) -> i32 {
+    assert!(index < positions.len(), "index out of bounds");
+
+    let mut first = 0;
+
+    for cluster in cluster(positions, separation).vec {
+        if index < first + cluster.len {
+            return cluster.start + separation * (index - first) as i32;
+        }
+
+        first += cluster.len;
+    }
+
+    unreachable!("clusters contain every label")
+}
+
+/// Places labels, respecting a minimum separation, returning a signature of the separation and
+/// permitted positions without collecting the permitted positions.
+///
+/// The signature is a 64-bit FNV-1a hash, so it is stable across platforms and versions of Rust,
+/// and placements with the same separation and permitted positions have the same signature.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let signature = vertical_label_placement::place_signature(&[-1, 1], 10);
+///
+/// assert_eq!(vertical_label_placement::place_signature(&[0, 0], 10), signature);
+/// assert_ne!(vertical_label_placement::place_signature(&[0, 20], 10), signature);
+/// # }
+/// ```
+pub fn place_signature(positions: &[i32], separation: i32) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = |hash: u64, value: i32| {
+        value.to_le_bytes().iter().fold(hash, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        })
+    };
+
+    cluster(positions, separation)
+        .into_positions()
+        .fold(hash(OFFSET_BASIS, separation), hash)
+}
+
+/// Places labels in two independent columns, respecting a minimum separation within each column.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let (left, right) = vertical_label_placement::place_columns(&[0, 20], &[10, 10], 10);
+///
+/// assert_eq!([0, 20], *left);
+/// assert_eq!([5, 15], *right);
+/// # }
+/// ```
+pub fn place_columns(left: &[i32], right: &[i32], separation: i32) -> (Vec<i32>, Vec<i32>) {
+    (place(left, separation), place(right, separation))
+}
+
+/// Places labels, respecting a minimum separation, merging each label's cluster with at most
+/// `lookback` previous clusters.
+///
+/// If a cluster still overlaps the previous cluster after `lookback` merges, it is moved towards
+/// higher positions until it is separated from the previous cluster, rather than being merged and
+/// balanced. This bounds the work for each label, but the maximum absolute offset may then be
+/// greater than for `place()`, by up to the extent of the unmerged overlap. If `lookback` is at
+/// least the number of labels, the result is identical to `place()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 10, 20, 30, 31];
+///
+/// let exact_positions = vertical_label_placement::place_approximate(&preferred_positions, 10, 4);
+/// let approximate_positions =
+///     vertical_label_placement::place_approximate(&preferred_positions, 10, 1);
+///
+/// assert_eq!([-5, 5, 15, 25, 35], *exact_positions);
+/// assert_eq!([0, 10, 20, 30, 40], *approximate_positions);
+/// # }
+/// ```
+pub fn place_approximate(positions: &[i32], separation: i32, lookback: usize) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+        let mut merges = 0;
+
+        while merges < lookback {
+            match clusters.pop_if_not_separate(cluster) {
+                Some(previous) => cluster = Cluster::merge(previous, cluster, separation),
+                None => break,
+            }
+
+            merges += 1;
+        }
+
+        if let Some(previous) = clusters.vec.last() {
+            if previous.end + separation > cluster.start {
+                cluster.shift(previous.end + separation - cluster.start);
+            }
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, with the
+/// specified behaviour if the limits do not provide sufficient space for all the labels, returning
+/// whether the minimum separation was violated alongside the permitted positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Overflow;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let (permitted_positions, violated) = vertical_label_placement::place_with_limits_checked(
+///     &preferred_positions,
+///     10,
+///     -10,
+///     10,
+///     Overflow::Clamp
+/// );
+///
+/// assert_eq!([-10, -10, 0, 10], *permitted_positions);
+/// assert!(violated);
+/// # }
+/// ```
+pub fn place_with_limits_checked(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    overflow: Overflow,
+) -> (Vec<i32>, bool) {
+    let positions = place_with_overflow(positions, separation, min, max, overflow);
+    let violated = !are_separated(&positions, separation);
+
+    (positions, violated)
+}
+
+/// Places labels, respecting a minimum separation, placing labels with equal preferred positions
+/// in ascending order of rank.
+///
+/// The permitted positions are returned in the same order as the preferred positions. Labels with
+/// equal preferred positions and equal ranks are placed in input order.
+///
+/// # Panics
+///
+/// Panics if `rank` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_ordered(&preferred_positions, 10, &[2, 0, 1]);
+///
+/// assert_eq!([10, -10, 0], *permitted_positions);
+/// # }
+/// ```
+pub fn place_ordered(positions: &[i32], separation: i32, rank: &[u32]) -> Vec<i32> {
+    assert_eq!(positions.len(), rank.len(), "rank count does not match");
+
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by_key(|&index| (positions[index], rank[index]));
+
+    let sorted: Vec<i32> = order.iter().map(|&index| positions[index]).collect();
+
+    let mut placed = vec![0; positions.len()];
+    for (index, position) in order.into_iter().zip(place(&sorted, separation)) {
+        placed[index] = position;
+    }
+
+    placed
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, minimising
+/// the maximum weighted absolute offset of any label from its preferred position.
+///
+/// Each cluster is first balanced to minimise the maximum of `weights[i] * |offset|` over its
+/// labels, and then shifted to respect the limits, so the limits take precedence over the weights.
+/// Labels with a weight of zero do not affect the balance, unless every label in a cluster has a
+/// weight of zero, in which case the cluster is balanced as for `place_with_limits()`. With equal
+/// weights, the result is identical to `place_with_limits()`. Balancing a cluster takes time
+/// proportional to the number of labels in it, so this is slower than `place_with_limits()` for
+/// large clusters.
+///
+/// # Panics
+///
+/// Panics if `weights` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0];
+///
+/// let unlimited_positions =
+///     vertical_label_placement::place_full(&preferred_positions, 10, &[3, 1], -100, 100);
+/// let limited_positions =
+///     vertical_label_placement::place_full(&preferred_positions, 10, &[3, 1], 0, 100);
+///
+/// assert_eq!([-2, 8], *unlimited_positions);
+/// assert_eq!([0, 10], *limited_positions);
+/// # }
+/// ```
+pub fn place_full(
+    positions: &[i32],
+    separation: i32,
+    weights: &[u32],
+    min: i32,
+    max: i32,
+) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        weights.len(),
+        "weight count does not match"
+    );
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position).limit(min, max);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation);
+
+            let first = index + 1 - cluster.len;
+            let weights = &weights[first..=index];
+
+            cluster = if weights.iter().all(|weight| *weight == 0) {
+                cluster.balance()
+            } else {
+                let start = weighted_start(
+                    &positions[first..=index],
+                    weights,
+                    separation,
+                    cluster.start,
+                );
+                cluster.shift(start - cluster.start);
+                cluster
+            };
+
+            cluster = cluster.limit(min, max);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, returning the offset of each label from its
+/// preferred position alongside the permitted positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let (permitted_positions, offsets) =
+///     vertical_label_placement::place_positions_and_offsets(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// assert_eq!([-5, -4, 4, 5], *offsets);
+/// # }
+/// ```
+pub fn place_positions_and_offsets(positions: &[i32], separation: i32) -> (Vec<i32>, Vec<i32>) {
+    let mut placed = Vec::with_capacity(positions.len());
+    let mut offsets = Vec::with_capacity(positions.len());
+
+    for (position, preferred) in cluster(positions, separation)
+        .into_positions()
+        .zip(positions)
+    {
+        placed.push(position);
+        offsets.push(position - preferred);
+    }
+
+    (placed, offsets)
+}
+
+/// Places labels, respecting a minimum separation, in the direction given by a comparator.
+///
+/// The comparator must order positions either ascending or descending, and the preferred positions
+/// must be sorted by it. With a descending comparator, each label is placed at least the minimum
+/// separation below the previous label, as for an inverted axis.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![10, 1, -1, -10];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_by(&preferred_positions, 10, |a, b| b.cmp(&a));
+///
+/// assert_eq!([15, 5, -5, -15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_by(
+    positions: &[i32],
+    separation: i32,
+    cmp: impl Fn(i32, i32) -> Ordering,
+) -> Vec<i32> {
+    if cmp(0, 1) != Ordering::Greater {
+        return place(positions, separation);
+    }
+
+    // Bitwise negation reverses the order of positions while preserving the distances between
+    // them, and unlike arithmetic negation it cannot overflow
+    let reversed: Vec<i32> = positions.iter().map(|position| !position).collect();
+
+    place(&reversed, separation)
+        .into_iter()
+        .map(|position| !position)
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, returning for each cluster the range of start
+/// positions to which it could be moved, with the other clusters fixed, without increasing the
+/// maximum absolute offset of any label or violating the minimum separation.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 100];
+///
+/// let ranges = vertical_label_placement::cluster_balance_ranges(&preferred_positions, 10);
+///
+/// assert_eq!([-5..=-5, 95..=105], *ranges);
+/// # }
+/// ```
+pub fn cluster_balance_ranges(positions: &[i32], separation: i32) -> Vec<RangeInclusive<i32>> {
+    let clusters = cluster(positions, separation).vec;
+
+    let max_offset = clusters
+        .iter()
+        .map(|cluster| max(-cluster.min_offset, cluster.max_offset))
+        .max()
+        .unwrap_or(0);
+
+    (0..clusters.len())
+        .map(|index| {
+            let cluster = clusters[index];
+
+            let mut low = cluster.start - max_offset - cluster.min_offset;
+            let mut high = cluster.start + max_offset - cluster.max_offset;
+
+            if let Some(previous) = index.checked_sub(1).map(|previous| clusters[previous]) {
+                low = max(low, previous.end + separation);
+            }
+
+            if let Some(next) = clusters.get(index + 1) {
+                high = min(
+                    high,
+                    next.start - separation - (cluster.end - cluster.start),
+                );
+            }
+
+            low..=high
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, borrowing the preferred positions if they are
+/// already sufficiently separated.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use std::borrow::Cow;
+///
+/// let separated_positions = vertical_label_placement::place_cow(&[-10, 0, 10], 10);
+/// let permitted_positions = vertical_label_placement::place_cow(&[-10, -1, 1, 10], 10);
+///
+/// assert!(matches!(separated_positions, Cow::Borrowed(_)));
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_cow(positions: &[i32], separation: i32) -> Cow<'_, [i32]> {
+    if are_separated(positions, separation) {
+        Cow::Borrowed(positions)
+    } else {
+        Cow::Owned(cluster(positions, separation).positions())
+    }
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, spreading
+/// the labels apart towards a target separation where the limits provide sufficient space.
+///
+/// The largest separation between `min_separation` and `target_separation` for which the labels
+/// fit within the limits is used. If they do not fit even at the minimum separation, the result is
+/// identical to `place_with_limits()` with the minimum separation.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 11];
+///
+/// let spread_positions =
+///     vertical_label_placement::place_spread(&preferred_positions, 10, 20, -100, 100);
+/// let limited_positions =
+///     vertical_label_placement::place_spread(&preferred_positions, 10, 20, 0, 15);
+///
+/// assert_eq!([-5, 15], *spread_positions);
+/// assert_eq!([0, 15], *limited_positions);
+/// # }
+/// ```
+pub fn place_spread(
+    positions: &[i32],
+    min_separation: i32,
+    target_separation: i32,
+    min: i32,
+    max: i32,
+) -> Vec<i32> {
+    let fits = |placed: &[i32]| placed.iter().all(|position| (min..=max).contains(position));
+
+    let mut low = min_separation;
+    let mut high = min_separation.max(target_separation);
+
+    while low < high {
+        let separation = low + (high - low + 1) / 2;
+
+        if fits(&place_with_limits(positions, separation, min, max)) {
+            low = separation;
+        } else {
+            high = separation - 1;
+        }
+    }
+
+    place_with_limits(positions, low, min, max)
+}
+
+/// Places labels, respecting a minimum separation, minimising the total cost of the offsets of the
+/// labels from their preferred positions within each cluster.
+///
+/// The cost function must be convex, so it must be non-increasing for negative offsets and
+/// non-decreasing for positive offsets, with its minimum at or near zero. For example, `|offset|`
+/// places each cluster at the median of its labels' preferred positions, and `offset * offset`
+/// places each cluster using least squares. Each merge evaluates the cost function for every
+/// label in the merged cluster, so this is slower than `place()` for large clusters.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0, 15];
+///
+/// let permitted_positions = vertical_label_placement::place_with_cost(
+///     &preferred_positions,
+///     10,
+///     |offset| i64::from(offset) * i64::from(offset)
+/// );
+///
+/// assert_eq!([-11, -1, 9, 19], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_cost(positions: &[i32], separation: i32, cost: impl Fn(i32) -> i64) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation);
+
+            let first = index + 1 - cluster.len;
+            let start = cost_start(&positions[first..=index], separation, cluster.start, &cost);
+
+            cluster.shift(start - cluster.start);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Assigns labels to a fixed number of evenly spaced slots, with at most one label per slot,
+/// returning the index of the slot chosen for each label.
+///
+/// Slot `i` is at position `slot_start + i * slot_step`. Labels are assigned from lowest to
+/// highest, each taking the nearest free slot that leaves enough slots for the remaining labels.
+/// This greedy assignment does not always minimise the total displacement.
+///
+/// # Panics
+///
+/// Panics if there are more labels than slots or if `slot_step` is not positive.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, 9, 11, 40];
+///
+/// let slots = vertical_label_placement::place_in_slots(&preferred_positions, 4, 0, 10);
+///
+/// assert_eq!([0, 1, 2, 3], *slots);
+/// # }
+/// ```
+pub fn place_in_slots(
+    positions: &[i32],
+    slot_count: usize,
+    slot_start: i32,
+    slot_step: i32,
+) -> Vec<usize> {
+    assert!(positions.len() <= slot_count, "more labels than slots");
+    assert!(slot_step > 0, "slot step is not positive");
+
+    let step = i64::from(slot_step);
+    let mut next = 0;
+
+    positions
+        .iter()
+        .enumerate()
+        .map(|(index, &position)| {
+            let offset = i64::from(position) - i64::from(slot_start);
+            let nearest = (offset + step / 2).div_euclid(step);
+            let last = slot_count - (positions.len() - index);
+
+            let slot = usize::try_from(nearest)
+                .map_or(0, |slot| slot)
+                .clamp(next, last);
+            next = slot + 1;
+            slot
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, such that no label lies within a zone from
+/// `zone_start` (inclusive) to `zone_end` (exclusive).
+///
+/// The labels are first placed as by `place()`. Each cluster that intersects the zone is then
+/// assigned to whichever side of the zone it is nearer, and the labels on each side are placed
+/// again with the zone as a limit.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 10, 20, 30];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_avoiding_zone(&preferred_positions, 10, 5, 25);
+///
+/// assert_eq!([-6, 4, 25, 35], *permitted_positions);
+/// # }
+/// ```
+pub fn place_avoiding_zone(
+    positions: &[i32],
+    separation: i32,
+    zone_start: i32,
+    zone_end: i32,
+) -> Vec<i32> {
+    if zone_start >= zone_end {
+        return place(positions, separation);
+    }
+
+    let mut split = 0;
+
+    for cluster in &cluster(positions, separation).vec {
+        let below = i64::from(cluster.end) - i64::from(zone_start) + 1;
+        let above = i64::from(zone_end) - i64::from(cluster.start);
+
+        if below <= 0 || (above > 0 && below <= above) {
+            split += cluster.len;
+        }
+    }
+
+    let mut placed = place_with_limits(
+        &positions[..split],
+        separation,
+        i32::MIN,
+        zone_start.saturating_sub(1),
+    );
+
+    let min = match placed.last() {
+        Some(last) => max(zone_end, last.saturating_add(separation)),
+        None => zone_end,
+    };
+
+    placed.extend(place_with_limits(
+        &positions[split..],
+        separation,
+        min,
+        i32::MAX,
+    ));
+    placed
+}
+
+/// Places labels, respecting a minimum separation, by writing the permitted positions to a slice.
+///
+/// # Panics
+///
+/// Panics if `out` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+/// let mut permitted_positions = [0; 4];
+///
+/// vertical_label_placement::place_slice_into(&preferred_positions, 10, &mut permitted_positions);
+///
+/// assert_eq!([-15, -5, 5, 15], permitted_positions);
+/// # }
+/// ```
+pub fn place_slice_into(positions: &[i32], separation: i32, out: &mut [i32]) {
+    assert_eq!(positions.len(), out.len(), "output length does not match");
+    cluster(positions, separation).write_positions(out);
+}
+
+/// Places labels, respecting a minimum separation, such that neighbouring labels in the same group
+/// are placed in the same cluster.
+///
+/// Labels sharing a group are placed exactly the minimum separation apart, even if their preferred
+/// positions are further apart than that. Only neighbouring labels are grouped, so a group should
+/// occupy a contiguous run of the sorted preferred positions.
+///
+/// # Panics
+///
+/// Panics if `group_ids` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 30, 100];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_grouped(&preferred_positions, 10, &[1, 1, 2]);
+///
+/// assert_eq!([10, 20, 100], *permitted_positions);
+/// # }
+/// ```
+pub fn place_grouped(positions: &[i32], separation: i32, group_ids: &[u32]) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        group_ids.len(),
+        "group count does not match"
+    );
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position);
+
+        loop {
+            let first = index + 1 - cluster.len;
+
+            let previous = if first > 0 && group_ids[first - 1] == group_ids[first] {
+                clusters.vec.pop()
+            } else {
+                clusters.pop_if_not_separate(cluster)
+            };
+
+            match previous {
+                Some(previous) => cluster = Cluster::merge(previous, cluster, separation),
+                None => break,
+            }
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Returns whether positions are a valid placement: sorted in ascending order, with each label
+/// separated from the previous label by at least the minimum separation, and lying within the
+/// limits, if any.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::{is_valid_placement, place};
+///
+/// let permitted_positions = place(&[-10, -1, 1, 10], 10);
+///
+/// assert!(is_valid_placement(&permitted_positions, 10, None, None));
+/// assert!(!is_valid_placement(&permitted_positions, 10, None, Some(10)));
+/// # }
+/// ```
+pub fn is_valid_placement(
+    placed: &[i32],
+    separation: i32,
+    min: Option<i32>,
+    max: Option<i32>,
+) -> bool {
+    let sorted = placed.windows(2).all(|pair| pair[0] <= pair[1]);
+
+    let separated = are_separated(placed, separation);
+
+    let limited = placed.iter().all(|position| {
+        min.is_none_or(|min| *position >= min) && max.is_none_or(|max| *position <= max)
+    });
+
+    sorted && separated && limited
+}
+
+/// Places labels, respecting a minimum separation, such that no label is placed more than `max_up`
+/// above (towards higher positions) or `max_down` below (towards lower positions) its preferred
+/// position.
+///
+/// Moving one label in a cluster moves the whole cluster, so a cluster that exceeds one bound is
+/// moved as far as necessary towards the other, which may cause it to merge with the previous
+/// cluster.
+///
+/// # Errors
+///
+/// Returns `PlacementError::OffsetCapExceeded` if some cluster cannot satisfy both bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_with_offset_bounds(&preferred_positions, 10, 2, 20);
+///
+/// assert_eq!(Ok(vec![-8, 2]), permitted_positions);
+/// # }
+/// ```
+pub fn place_with_offset_bounds(
+    positions: &[i32],
+    separation: i32,
+    max_up: i32,
+    max_down: i32,
+) -> Result<Vec<i32>, PlacementError> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position)
+            .bound_offsets(max_up, max_down)
+            .ok_or(PlacementError::OffsetCapExceeded)?;
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation)
+                .bound_offsets(max_up, max_down)
+                .ok_or(PlacementError::OffsetCapExceeded)?;
+        }
+
+        clusters.push(cluster);
+    }
+
+    Ok(clusters.positions())
+}
+
+/// Generates sorted preferred positions for benchmarks and regression tests, with the labels
+/// crowded around a number of cluster centres.
+///
+/// The positions are generated by a fixed pseudorandom number generator, so the same arguments
+/// always generate the same positions. The cluster centres lie between zero and ten times the
+/// number of labels, and each label lies within twenty of its centre. If `clusters` is zero, the
+/// labels are instead spread evenly at random.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// # #[cfg(feature = "bench")]
+/// # {
+/// use vertical_label_placement::generate_positions;
+///
+/// let positions = generate_positions(1000, 20, 1);
+///
+/// assert_eq!(1000, positions.len());
+/// assert_eq!(positions, generate_positions(1000, 20, 1));
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "bench")]
+pub fn generate_positions(count: usize, clusters: usize, seed: u64) -> Vec<i32> {
+    let mut state = seed ^ RANDOM_SEED;
+    let mut next = move |bound: u64| next_random(&mut state) % bound.max(1);
+
+    let span = count as u64 * 10;
+    let centres: Vec<u64> = (0..clusters).map(|_| next(span)).collect();
+
+    let mut positions: Vec<i32> = (0..count)
+        .map(|_| match centres.len() {
+            0 => next(span) as i32,
+            len => (centres[next(len as u64) as usize] + next(41)) as i32 - 20,
+        })
+        .collect();
+
+    positions.sort_unstable();
+    positions
+}
+
+/// Places labels with heights, respecting a minimum gap between neighbouring labels, returning the
+/// interval occupied by each label.
+///
+/// Each label occupies the half-open interval from `center - height / 2` (inclusive) to
+/// `center + (height - height / 2)` (exclusive), as in `place_sized_with_limits()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let centers = vec![0, 5];
+/// let heights = vec![10, 20];
+///
+/// let intervals = vertical_label_placement::place_sized_intervals(&centers, &heights, 2);
+///
+/// assert_eq!([(-11, -1), (1, 21)], *intervals);
+/// # }
+/// ```
+pub fn place_sized_intervals(centers: &[i32], heights: &[i32], gap: i32) -> Vec<(i32, i32)> {
+    let centers = place_with_separations(
+        centers,
+        |index| heights[index - 1] - heights[index - 1] / 2 + heights[index] / 2 + gap,
+        |_| (i32::MIN, i32::MAX),
+    );
+
+    centers
+        .iter()
+        .zip(heights)
+        .map(|(center, height)| (center - height / 2, center + (height - height / 2)))
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, returning the offset of each label from its
+/// preferred position as an `f32`.
+///
+/// An `f32` represents every integer up to 2<sup>24</sup> (16,777,216) in magnitude exactly, so
+/// larger offsets are rounded to the nearest representable value.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let offsets = vertical_label_placement::place_offsets_f32(&preferred_positions, 10);
+///
+/// assert_eq!([-5.0, -4.0, 4.0, 5.0], *offsets);
+/// # }
+/// ```
+pub fn place_offsets_f32(positions: &[i32], separation: i32) -> Vec<f32> {
+    cluster(positions, separation)
+        .into_positions()
+        .zip(positions)
+        .map(|(position, preferred)| (i64::from(position) - i64::from(*preferred)) as f32)
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, near a viewport, leaving distant labels at
+/// their preferred positions.
+///
+/// The labels whose preferred positions lie within the viewport are placed as by `place()`, and the
+/// neighbouring labels that would not be separated from the result are added until no more are
+/// needed. This is an approximation: it matches `place()` when the labels at the edges of this
+/// region are separated from the labels beyond them, but the labels beyond are never moved, so they
+/// may not be separated from each other.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-1000, -1000, 0, 1, 2, 1000, 1000];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_with_viewport(&preferred_positions, 10, 0..10);
+///
+/// assert_eq!([-1000, -1000, -9, 1, 11, 1000, 1000], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_viewport(positions: &[i32], separation: i32, viewport: Range<i32>) -> Vec<i32> {
+    let separation_i64 = i64::from(separation);
+
+    let mut start = positions.partition_point(|position| *position < viewport.start);
+    let mut end = start + positions[start..].partition_point(|position| *position < viewport.end);
+
+    let mut placed = place(&positions[start..end], separation);
+
+    while let (Some(first), Some(last)) = (placed.first(), placed.last()) {
+        let new_start = positions[..start]
+            .partition_point(|position| i64::from(*position) + separation_i64 <= i64::from(*first));
+
+        let new_end = end
+            + positions[end..].partition_point(|position| {
+                i64::from(*last) + separation_i64 > i64::from(*position)
+            });
+
+        if new_start == start && new_end == end {
+            break;
+        }
+
+        start = new_start;
+        end = new_end;
+        placed = place(&positions[start..end], separation);
+    }
+
+    let mut result = positions.to_vec();
+    result[start..end].copy_from_slice(&placed);
+    result
+}
+
+/// Merges two placements into one, respecting a minimum separation across the seam between them.
+///
+/// The concatenation of the two placements is placed as by `place()`, treating the permitted
+/// positions as preferred positions, so any labels that are not separated across the seam are
+/// balanced about their current positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let left = vec![0, 10];
+/// let right = vec![15, 25];
+///
+/// let permitted_positions = vertical_label_placement::merge_placements(&left, &right, 10);
+///
+/// assert_eq!([-3, 7, 17, 27], *permitted_positions);
+/// # }
+/// ```
+pub fn merge_placements(left: &[i32], right: &[i32], separation: i32) -> Vec<i32> {
+    let mut positions = Vec::with_capacity(left.len() + right.len());
+    positions.extend_from_slice(left);
+    positions.extend_from_slice(right);
+
+    place(&positions, separation)
+}
+
+/// Places labels, respecting a minimum separation, with the labels in each cluster spaced by a
+/// larger display separation.
+///
+/// Clusters are formed and balanced as by `place()` using the minimum separation, and then each
+/// cluster is expanded about its centre to use the display separation. An expanded cluster can
+/// extend beyond the space assumed while merging, so any label that is then not separated from the
+/// previous label by the minimum separation is moved towards higher positions until it is.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0, 100];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_display(&preferred_positions, 10, 12);
+///
+/// assert_eq!([-12, 0, 12, 100], *permitted_positions);
+/// # }
+/// ```
+pub fn place_display(positions: &[i32], min_separation: i32, display_separation: i32) -> Vec<i32> {
+    let mut placed: Vec<i32> = Vec::with_capacity(positions.len());
+
+    for cluster in cluster(positions, min_separation).vec {
+        let extra = (display_separation - min_separation) * (cluster.len - 1) as i32;
+        let start = cluster.start - extra / 2;
+
+        for index in 0..cluster.len {
+            let position = start + display_separation * index as i32;
+
+            placed.push(match placed.last() {
+                Some(previous) => max(position, previous + min_separation),
+                None => position,
+            });
+        }
+    }
+
+    placed
+}
+
+/// Places labels on a circular axis, such as a gauge, where position `circumference` is equivalent
+/// to position zero, respecting a minimum separation in both directions around the circle.
+///
+/// The preferred positions must be sorted in ascending order and lie from zero (inclusive) to
+/// `circumference` (exclusive). The circle is cut at the largest gap between neighbouring labels,
+/// and the labels are then placed as by `place()` along the resulting line. If the labels at the
+/// two ends are then not separated across the cut, they are placed again with limits that centre
+/// them within a span of `circumference - separation`. The permitted positions are returned in the
+/// same order as the preferred positions, reduced to lie from zero to `circumference`, so labels
+/// placed across position zero are no longer in ascending order.
+///
+/// If the labels do not fit around the circle, which is the case when the number of labels
+/// multiplied by `separation` exceeds `circumference`, the minimum separation cannot be respected.
+/// Neighbouring labels are still placed the minimum separation apart going around the circle from
+/// the cut, but the last labels wrap past the first, so labels may coincide.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 1, 358, 359];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_circular(&preferred_positions, 10, 360);
+///
+/// assert_eq!([4, 14, 344, 354], *permitted_positions);
+/// # }
+/// ```
+pub fn place_circular(positions: &[i32], separation: i32, circumference: i32) -> Vec<i32> {
+    let len = positions.len();
+
+    if len < 2 {
+        return positions.to_vec();
+    }
+
+    let gap = |index: usize| match index + 1 {
+        next if next == len => positions[0] + circumference - positions[index],
+        next => positions[next] - positions[index],
+    };
+
+    let start = (0..len)
+        .max_by_key(|index| gap(*index))
+        .map_or(0, |index| index + 1)
+        % len;
+
+    let unwrapped: Vec<i32> = (0..len)
+        .map(|index| match (start + index) % len {
+            index if index < start => positions[index] + circumference,
+            index => positions[index],
+        })
+        .collect();
+
+    let mut placed = place(&unwrapped, separation);
+
+    if placed[len - 1] - placed[0] > circumference - separation {
+        let min = (placed[0] + placed[len - 1] - circumference + separation) / 2;
+        placed = place_with_limits(
+            &unwrapped,
+            separation,
+            min,
+            min + circumference - separation,
+        );
+    }
+
+    let mut result = vec![0; len];
+
+    for (index, position) in placed.into_iter().enumerate() {
+        result[(start + index) % len] = position.rem_euclid(circumference);
+    }
+
+    result
+}
+
+/// Returns whether each label is already separated from the previous label by at least the minimum
+/// separation, in which case `place()` would leave every label at its preferred position.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::are_separated;
+///
+/// const SEPARATED: bool = are_separated(&[0, 10, 20], 10);
+///
+/// assert!(SEPARATED);
+/// assert!(!are_separated(&[0, 5, 20], 10));
+/// # }
+/// ```
+pub const fn are_separated(positions: &[i32], separation: i32) -> bool {
+    let mut index = 1;
+
+    while index < positions.len() {
+        if (positions[index] as i64) - (positions[index - 1] as i64) < separation as i64 {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Places labels, respecting a minimum separation, with the specified rounding of the shift that
+/// balances each cluster.
+///
+/// When the offsets in a cluster cannot be balanced exactly, two shifts are equally good. The
+/// rounding mode determines which is chosen: `RoundMode::TowardZero` matches `place()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::{place_with_round, RoundMode};
+///
+/// assert_eq!([-6, 5], *place_with_round(&[0, 0], 11, RoundMode::TowardZero));
+/// assert_eq!([-5, 6], *place_with_round(&[0, 0], 11, RoundMode::TowardNegInf));
+/// # }
+/// ```
+pub fn place_with_round(positions: &[i32], separation: i32, round: RoundMode) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation).balance_rounded(round);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, both with and without minimum and maximum
+/// positions, returning the permitted positions with the limits followed by those without.
+///
+/// The two placements share their clusters until a cluster first crosses one of the limits, after
+/// which they continue separately, so the clusters are only merged once for both placements while
+/// every cluster lies within the limits. The results match those of `place_with_limits()` and
+/// `place()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let (limited, unlimited) = vertical_label_placement::place_with_and_without_limits(
+///     &preferred_positions,
+///     10,
+///     0,
+///     100
+/// );
+///
+/// assert_eq!([0, 10, 20, 30], *limited);
+/// assert_eq!([-15, -5, 5, 15], *unlimited);
+/// # }
+/// ```
+pub fn place_with_and_without_limits(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> (Vec<i32>, Vec<i32>) {
+    let within = |cluster: &Cluster| cluster.start >= min && cluster.end <= max;
+
+    let mut unlimited_clusters = ClusterList::new(separation, positions.len());
+    let mut popped = Vec::new();
+    let mut shared = positions.len();
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position);
+        popped.clear();
+
+        while within(&cluster) {
+            let Some(previous) = unlimited_clusters.pop_if_not_separate(cluster) else {
+                break;
+            };
+
+            popped.push(previous);
+            cluster = Cluster::merge(previous, cluster, separation);
+        }
+
+        if within(&cluster) {
+            unlimited_clusters.push(cluster);
+        } else {
+            unlimited_clusters.vec.extend(popped.drain(..).rev());
+            shared = index;
+            break;
+        }
+    }
+
+    if shared == positions.len() {
+        let positions = unlimited_clusters.positions();
+        return (positions.clone(), positions);
+    }
+
+    let mut limited_clusters = ClusterList::new(separation, positions.len());
+    limited_clusters
+        .vec
+        .extend_from_slice(&unlimited_clusters.vec);
+
+    for position in &positions[shared..] {
+        let mut limited = Cluster::new(*position).limit(min, max);
+        let mut unlimited = Cluster::new(*position);
+
+        while let Some(previous) = limited_clusters.pop_if_not_separate(limited) {
+            limited = Cluster::merge(previous, limited, separation).limit(min, max);
+        }
+
+        while let Some(previous) = unlimited_clusters.pop_if_not_separate(unlimited) {
+            unlimited = Cluster::merge(previous, unlimited, separation);
+        }
+
+        limited_clusters.push(limited);
+        unlimited_clusters.push(unlimited);
+    }
+
+    (limited_clusters.positions(), unlimited_clusters.positions())
+}
+
+/// Returns the offsets of the labels in each cluster from their preferred positions, respecting a
+/// minimum separation.
+///
+/// The labels in each cluster are contiguous within the preferred positions, so the offsets of the
+/// members are found from the preferred positions rather than being tracked while merging.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let offsets = vertical_label_placement::cluster_member_offsets(&preferred_positions, 10);
+///
+/// assert_eq!([vec![-5, -4, 4, 5], vec![0]], *offsets);
+/// # }
+/// ```
+pub fn cluster_member_offsets(positions: &[i32], separation: i32) -> Vec<Vec<i32>> {
+    let mut preferred = positions.iter();
+
+    cluster(positions, separation)
+        .vec
+        .into_iter()
+        .map(|cluster| {
+            cluster
+                .positions(separation)
+                .zip(preferred.by_ref())
+                .map(|(position, preferred)| position - preferred)
+                .collect()
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, without placing any label below its preferred
+/// position.
+///
+/// This is an alias for `place_greedy()`, which keeps the first label at its preferred position
+/// and moves every other label up only as far as necessary to be separated from the previous label.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 30, 31];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_anchor_first(&preferred_positions, 10);
+///
+/// assert_eq!([0, 10, 30, 40], *permitted_positions);
+/// # }
+/// ```
+pub fn place_anchor_first(positions: &[i32], separation: i32) -> Vec<i32> {
+    place_greedy(positions, separation)
+}
+
+/// Returns the maximum absolute offset of any label from its preferred position, respecting a
+/// minimum separation, without creating a vector of permitted positions.
+///
+/// The maximum absolute offset never decreases as the minimum separation increases.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let max_offset = vertical_label_placement::max_offset_only(&preferred_positions, 10);
+///
+/// assert_eq!(5, max_offset);
+/// # }
+/// ```
+pub fn max_offset_only(positions: &[i32], separation: i32) -> i32 {
+    cluster(positions, separation)
+        .vec
+        .iter()
+        .map(|cluster| {
+            max(
+                cluster.min_offset.unsigned_abs(),
+                cluster.max_offset.unsigned_abs(),
+            )
+        })
+        .max()
+        .map_or(0, |offset| i32::try_from(offset).unwrap_or(i32::MAX))
+}
+
+/// Returns the largest minimum separation for which no label is offset from its preferred position
+/// by more than a maximum offset.
+///
+/// As `max_offset_only()` never decreases as the separation increases, the separation is found by
+/// a binary search. Neighbouring labels are offset by at least half the amount by which they are
+/// closer than the separation, which bounds the search. If there are fewer than two labels, any
+/// separation is permitted and `i32::MAX` is returned. If even a separation of zero offsets some
+/// label by more than the maximum offset, zero is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let separation =
+///     vertical_label_placement::max_separation_within_offset(&preferred_positions, 5);
+///
+/// assert_eq!(10, separation);
+/// # }
+/// ```
+pub fn max_separation_within_offset(positions: &[i32], max_offset: i32) -> i32 {
+    let Some(min_gap) = positions
+        .windows(2)
+        .map(|pair| i64::from(pair[1]) - i64::from(pair[0]))
+        .min()
+    else {
+        return i32::MAX;
+    };
+
+    let mut low = 0;
+    let mut high = (i64::from(max_offset) * 2 + min_gap + 1).clamp(0, i64::from(i32::MAX)) as i32;
+
+    while low < high {
+        let separation = low + (high - low + 1) / 2;
+
+        if max_offset_only(positions, separation) <= max_offset {
+            low = separation;
+        } else {
+            high = separation - 1;
+        }
+    }
+
+    low
+}
+
+/// Places labels, respecting a minimum separation, returning the permitted positions in descending
+/// order.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_reversed(&preferred_positions, 10);
+///
+/// assert_eq!([15, 5, -5, -15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_reversed(positions: &[i32], separation: i32) -> Vec<i32> {
+    cluster(positions, separation).reversed_positions()
+}
+
+/// Places labels, respecting a minimum separation only between labels in the same category.
+///
+/// The labels in each category are placed independently, as by `place()`, so labels in different
+/// categories may coincide. The permitted positions are returned in the same order as the preferred
+/// positions.
+///
+/// # Panics
+///
+/// Panics if `categories` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 1, 1];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_by_category(&preferred_positions, 10, &[1, 2, 1, 2]);
+///
+/// assert_eq!([-5, -5, 5, 5], *permitted_positions);
+/// # }
+/// ```
+pub fn place_by_category(positions: &[i32], separation: i32, categories: &[u32]) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        categories.len(),
+        "category count does not match"
+    );
+
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by_key(|index| categories[*index]);
+
+    let mut placed = vec![0; positions.len()];
+
+    for indices in order.chunk_by(|first, second| categories[*first] == categories[*second]) {
+        let category_positions: Vec<i32> = indices.iter().map(|index| positions[*index]).collect();
+
+        for (index, position) in indices.iter().zip(place(&category_positions, separation)) {
+            placed[*index] = position;
+        }
+    }
+
+    placed
+}
+
+/// Places labels, respecting a minimum separation, returning an explanation of the placement of
+/// each label.
+///
+/// A label is moved only if it belongs to a cluster with other labels, so an explanation with a
+/// non-zero offset always has more than one cluster member.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Explanation;
+///
+/// let preferred_positions = vec![-10, 100];
+///
+/// let explanations = vertical_label_placement::explain(&preferred_positions, 10);
+///
+/// assert_eq!(
+///     [
+///         Explanation { moved_by: 0, cluster_index: 0, cluster_member_count: 1 },
+///         Explanation { moved_by: 0, cluster_index: 1, cluster_member_count: 1 },
+///     ],
+///     *explanations
+/// );
+/// # }
+/// ```
+pub fn explain(positions: &[i32], separation: i32) -> Vec<Explanation> {
+    let mut preferred = positions.iter();
+    let mut explanations = Vec::with_capacity(positions.len());
+
+    for (cluster_index, cluster) in cluster(positions, separation).vec.into_iter().enumerate() {
+        let cluster_member_count = cluster.len;
+
+        for (position, preferred) in cluster.positions(separation).zip(preferred.by_ref()) {
+            explanations.push(Explanation {
+                moved_by: position - preferred,
+                cluster_index,
+                cluster_member_count,
+            });
+        }
+    }
+
+    explanations
+}
+
+/// Places labels, respecting a minimum separation, minimising the maximum weighted absolute offset
+/// of any label from its preferred position, with fractional weights.
+///
+/// This is as `place_full()` without limits, except that the weights are `f64` values. Labels with
+/// a weight of zero do not affect the balance, unless every label in a cluster has a weight of
+/// zero, in which case the cluster is balanced as for `place()`.
+///
+/// # Panics
+///
+/// Panics if `weights` is not the same length as `positions`, or if any weight is negative or NaN.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_weighted_f64(&preferred_positions, 10, &[0.9, 0.1]);
+///
+/// assert_eq!([-1, 9], *permitted_positions);
+/// # }
+/// ```
+pub fn place_weighted_f64(positions: &[i32], separation: i32, weights: &[f64]) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        weights.len(),
+        "weight count does not match"
+    );
+    assert!(
+        weights.iter().all(|weight| *weight >= 0.0),
+        "weight is negative or NaN"
+    );
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation);
+
+            let first = index + 1 - cluster.len;
+            let weights = &weights[first..=index];
+
+            cluster = if weights.iter().all(|weight| *weight == 0.0) {
+                cluster.balance()
+            } else {
+                let start = weighted_start_f64(
+                    &positions[first..=index],
+                    weights,
+                    separation,
+                    cluster.start,
+                );
+                cluster.shift(start - cluster.start);
+                cluster
+            };
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels positioned by their baselines, respecting a minimum gap between neighbouring
+/// labels and minimum and maximum positions for the edges of the labels.
+///
+/// Each label extends from `baseline - ascent` to `baseline + descent`, so labels are separated by
+/// the descent of the previous label, the gap and the ascent of the next label, and the edges of
+/// each label are kept between `min` and `max`. As with `place_with_limits()`, if the limits do
+/// not provide sufficient space for all the labels, only the maximum will be respected.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let baselines = vec![0, 95];
+/// let ascents = vec![8, 8];
+/// let descents = vec![2, 20];
+///
+/// let permitted_baselines = vertical_label_placement::place_sized_baseline(
+///     &baselines,
+///     &ascents,
+///     &descents,
+///     0,
+///     -100,
+///     100
+/// );
+///
+/// assert_eq!([0, 80], *permitted_baselines);
+/// # }
+/// ```
+pub fn place_sized_baseline(
+    baselines: &[i32],
+    ascents: &[i32],
+    descents: &[i32],
+    gap: i32,
+    min: i32,
+    max: i32,
+) -> Vec<i32> {
+    place_with_separations(
+        baselines,
+        |index| descents[index - 1] + gap + ascents[index],
+        |index| (min + ascents[index], max - descents[index]),
+    )
+}
+
+/// Places labels, respecting a minimum separation, towards a blend of their preferred positions and
+/// their previous permitted positions, reducing movement between successive placements.
+///
+/// Each label is placed as by `place()` with a target position of
+/// `preferred * (1 - inertia) + previous * inertia`, rounded to the nearest integer. An inertia of
+/// zero ignores the previous positions, and an inertia of one ignores the preferred positions. The
+/// previous positions must be sorted in ascending order, as they are when produced by this crate.
+///
+/// # Panics
+///
+/// Panics if `previous` is not the same length as `positions` or if `inertia` is not between zero
+/// and one.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 20, 40];
+/// let previous_positions = vec![-10, 0, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_with_inertia(
+///     &preferred_positions,
+///     10,
+///     &previous_positions,
+///     0.5
+/// );
+///
+/// assert_eq!([-5, 10, 25], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_inertia(
+    positions: &[i32],
+    separation: i32,
+    previous: &[i32],
+    inertia: f64,
+) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        previous.len(),
+        "previous count does not match"
+    );
+    assert!(
+        (0.0..=1.0).contains(&inertia),
+        "inertia is not between zero and one"
+    );
+
+    let targets: Vec<i32> = positions
+        .iter()
+        .zip(previous)
+        .map(|(preferred, previous)| {
+            (f64::from(*preferred) * (1.0 - inertia) + f64::from(*previous) * inertia).round()
+                as i32
+        })
+        .collect();
+
+    place(&targets, separation)
+}
+
+/// Returns the indices of the labels that start a new cluster, respecting a minimum separation.
+///
+/// The first label is not included, so the indices are the points at which the labels can be
+/// split between clusters.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let boundaries = vertical_label_placement::cluster_boundaries(&preferred_positions, 10);
+///
+/// assert_eq!([4], *boundaries);
+/// # }
+/// ```
+pub fn cluster_boundaries(positions: &[i32], separation: i32) -> Vec<usize> {
+    let clusters = cluster(positions, separation).vec;
+    let mut boundaries = Vec::with_capacity(clusters.len().saturating_sub(1));
+    let mut index = 0;
+
+    for cluster in &clusters[..clusters.len().saturating_sub(1)] {
+        index += cluster.len;
+        boundaries.push(index);
+    }
+
+    boundaries
+}
+
+/// Places labels, respecting a minimum separation that is overridden for particular pairs of
+/// neighbouring labels.
+///
+/// Each override `(index, separation)` sets the minimum separation between the label at `index`
+/// and the next label. Overrides for the last label or beyond are ignored, and if several
+/// overrides have the same index, the last is used.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_with_overrides(&preferred_positions, 10, &[(0, 30)]);
+///
+/// assert_eq!([-20, 10, 20], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_overrides(
+    positions: &[i32],
+    separation: i32,
+    overrides: &[(usize, i32)],
+) -> Vec<i32> {
+    let mut separations = vec![separation; positions.len()];
+
+    for (index, separation) in overrides {
+        if let Some(next) = index
+            .checked_add(1)
+            .and_then(|next| separations.get_mut(next))
+        {
+            *next = *separation;
+        }
+    }
+
+    place_with_separations(
+        positions,
+        |index| separations[index],
+        |_| (i32::MIN, i32::MAX),
+    )
+}
+
+/// Repairs positions that are not all separated by at least a minimum separation, moving labels as
+/// little as possible.
+///
+/// The positions are placed as by `place_in_place()`, treating them as preferred positions. Labels
+/// that are separated from their neighbours form clusters of their own and are not moved, so only
+/// the regions around violations of the minimum separation are changed.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let mut positions = vec![0, 20, 29, 40, 60];
+///
+/// vertical_label_placement::repair(&mut positions, 10);
+///
+/// assert_eq!([0, 19, 29, 40, 60], *positions);
+/// # }
+/// ```
+pub fn repair(placed: &mut [i32], separation: i32) {
+    if !are_separated(placed, separation) {
+        place_in_place(placed, separation);
+    }
+}
+
+/// Places labels, respecting a minimum separation, returning the offset of each permitted position
+/// from the nearest multiple of the separation.
+///
+/// Positions halfway between two multiples are measured from the higher multiple. If the
+/// separation is not positive, the offsets are measured from zero and so equal the permitted
+/// positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let deltas = vertical_label_placement::place_grid_deltas(&preferred_positions, 10);
+///
+/// assert_eq!([-5, -5, -5, -5, 0], *deltas);
+/// # }
+/// ```
+pub fn place_grid_deltas(positions: &[i32], separation: i32) -> Vec<i32> {
+    cluster(positions, separation)
+        .into_positions()
+        .map(|position| match separation {
+            separation if separation > 0 => {
+                let separation = i64::from(separation);
+                let position = i64::from(position);
+
+                (position - (position + separation / 2).div_euclid(separation) * separation) as i32
+            }
+            _ => position,
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, reducing the
+/// separation if the limits do not provide sufficient space for all the labels.
+///
+/// This is as `place_with_overflow()` with `Overflow::Compress`: if the labels do not fit, they are
+/// spaced evenly from the minimum limit to the maximum limit. This intentionally violates the
+/// minimum separation to keep every label within the limits.
+///
+/// # Panics
+///
+/// Panics if `min` is greater than `max`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_with_limits_compress(&preferred_positions, 10, 0, 10);
+///
+/// assert_eq!([0, 5, 10], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_limits_compress(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> Vec<i32> {
+    place_with_overflow(positions, separation, min, max, Overflow::Compress)
+}
+
+/// Places labels, respecting a minimum separation, merging neighbouring clusters only if a filter
+/// allows it.
+///
+/// The filter is called as `allow_merge(previous_end, next_start)` with the end position of the
+/// previous cluster and the start position of the next cluster whenever they are not sufficiently
+/// separated. If it returns `false`, the clusters are left unmerged, so the minimum separation is
+/// not respected between them and the permitted positions may not be sorted. If it always returns
+/// `true`, the result is identical to `place()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_with_merge_filter(
+///     &preferred_positions,
+///     10,
+///     |_, next_start| next_start < 10
+/// );
+///
+/// assert_eq!([-5, 5, 10], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_merge_filter(
+    positions: &[i32],
+    separation: i32,
+    allow_merge: impl Fn(i32, i32) -> bool,
+) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            if !allow_merge(previous.end, cluster.start) {
+                clusters.push(previous);
+                break;
+            }
+
+            cluster = Cluster::merge(previous, cluster, separation);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, returning the lowest and highest permitted
+/// positions, or `None` if there are no labels.
+///
+/// Applying limits no tighter than these bounds leaves the permitted positions unchanged, so they
+/// indicate where limits start to affect the placement.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let bounds = vertical_label_placement::free_bounds(&preferred_positions, 10);
+///
+/// assert_eq!(Some((-15, 100)), bounds);
+/// # }
+/// ```
+pub fn free_bounds(positions: &[i32], separation: i32) -> Option<(i32, i32)> {
+    let clusters = cluster(positions, separation).vec;
+
+    Some((clusters.first()?.start, clusters.last()?.end))
+}
+
+/// Places labels, respecting a minimum separation, shifting all the labels uniformly so that the
+/// mean permitted position is as close as possible to the mean preferred position.
+///
+/// The shift is limited so that the maximum absolute offset of any label is no greater than for
+/// `place()`, so minimising the maximum offset takes precedence over conserving the mean. As
+/// `place()` balances the cluster with the maximum absolute offset, this usually permits a shift
+/// of at most one, in which case the mean is conserved only approximately. The required shift is
+/// rounded to the nearest integer, rounding halfway cases towards zero.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 5, 5, 5];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_conserve_mean(&preferred_positions, 5);
+///
+/// assert_eq!([-7, -2, 3, 8, 13], *permitted_positions);
+/// # }
+/// ```
+pub fn place_conserve_mean(positions: &[i32], separation: i32) -> Vec<i32> {
+    let mut placed = place(positions, separation);
+
+    let offsets = || {
+        placed
+            .iter()
+            .zip(positions)
+            .map(|(placed, preferred)| placed - preferred)
+    };
+    let min_offset = offsets().min().unwrap_or(0);
+    let max_offset = offsets().max().unwrap_or(0);
+    let max_absolute = max(-min_offset, max_offset);
+
+    let difference: i64 = offsets().map(|offset| -i64::from(offset)).sum();
+    let count = placed.len().max(1) as i64;
+    let mut shift = difference / count;
+
+    if 2 * (difference % count).abs() > count {
+        shift += difference.signum();
+    }
+
+    let shift = shift.clamp(
+        i64::from(-max_absolute - min_offset),
+        i64::from(max_absolute - max_offset),
+    ) as i32;
+
+    for position in &mut placed {
+        *position += shift;
+    }
+
+    placed
+}
+
+/// Places labels, respecting a minimum separation, returning a map from the index of each label to
+/// its permitted position.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_map(&preferred_positions, 10);
+///
+/// assert_eq!(Some(&5), permitted_positions.get(&2));
+/// # }
+/// ```
+pub fn place_map(positions: &[i32], separation: i32) -> HashMap<usize, i32> {
+    let mut placed = HashMap::with_capacity(positions.len());
+    placed.extend(cluster(positions, separation).into_positions().enumerate());
+    placed
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, moving each
+/// cluster by a pseudo-random amount within its slack.
+///
+/// The labels are first placed as by `place_with_limits()`. Each cluster is then moved, in turn,
+/// to a start position chosen pseudo-randomly from those that respect the minimum separation from
+/// its neighbours and the limits without increasing the maximum absolute offset of any label. The
+/// same seed always gives the same permitted positions. Clusters that cannot be moved, including
+/// those that do not fit within the limits, are left unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 100];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_jittered(&preferred_positions, 10, -100, 200, 1);
+///
+/// assert_eq!([-5, 5], permitted_positions[..2]);
+/// assert!((95..=105).contains(&permitted_positions[2]));
+/// # }
+/// ```
+pub fn place_jittered(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    seed: u64,
+) -> Vec<i32> {
+    let mut state = seed ^ RANDOM_SEED;
+    let mut next = move |bound: u64| next_random(&mut state) % bound.max(1);
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position).limit(min, max);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation).limit(min, max);
+        }
+
+        clusters.push(cluster);
+    }
+
+    let max_offset = clusters
+        .vec
+        .iter()
+        .map(|cluster| i64::from(cluster.max_offset).max(-i64::from(cluster.min_offset)))
+        .max()
+        .unwrap_or(0);
+
+    for index in 0..clusters.vec.len() {
+        let cluster = clusters.vec[index];
+        let extent = i64::from(cluster.end) - i64::from(cluster.start);
+        let separation = i64::from(separation);
+
+        let mut low = (i64::from(cluster.start) - max_offset - i64::from(cluster.min_offset))
+            .max(i64::from(min));
+        let mut high = (i64::from(cluster.start) + max_offset - i64::from(cluster.max_offset))
+            .min(i64::from(max) - extent);
+
+        if let Some(previous) = index.checked_sub(1).map(|previous| clusters.vec[previous]) {
+            low = low.max(i64::from(previous.end) + separation);
+        }
+
+        if let Some(next) = clusters.vec.get(index + 1) {
+            high = high.min(i64::from(next.start) - separation - extent);
+        }
+
+        if low < high && (low..=high).contains(&i64::from(cluster.start)) {
+            let start = low + next((high - low + 1) as u64) as i64;
+            clusters.vec[index].shift((start - i64::from(cluster.start)) as i32);
+        }
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, returning metrics describing the placement
+/// alongside the permitted positions.
+///
+/// The cluster metrics are gathered while grouping the labels into clusters and the offset
+/// metrics while emitting the permitted positions, so no further pass over the labels is needed.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Metrics;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let (permitted_positions, metrics) =
+///     vertical_label_placement::place_with_metrics(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15, 100], *permitted_positions);
+/// assert_eq!(
+///     Metrics {
+///         max_offset: 5,
+///         total_displacement: 18,
+///         cluster_count: 2,
+///         max_cluster_size: 4
+///     },
+///     metrics
+/// );
+/// # }
+/// ```
+pub fn place_with_metrics(positions: &[i32], separation: i32) -> (Vec<i32>, Metrics) {
+    let mut clusters = ClusterList::new(separation, positions.len());
+    let mut metrics = Metrics::default();
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation);
+        }
+
+        metrics.max_cluster_size = max(metrics.max_cluster_size, cluster.len);
+        clusters.push(cluster);
+    }
+
+    metrics.cluster_count = clusters.vec.len();
+
+    let mut placed = Vec::with_capacity(positions.len());
+
+    for (position, preferred) in clusters.into_positions().zip(positions) {
+        let offset = (i64::from(position) - i64::from(*preferred)).unsigned_abs();
+
+        metrics.max_offset = max(metrics.max_offset, offset);
+        metrics.total_displacement += offset;
+        placed.push(position);
+    }
+
+    (placed, metrics)
+}
+
+/// Places labels, respecting a minimum separation expressed as a fraction of the span of the
+/// preferred positions.
+///
+/// The span is the distance from the first preferred position to the last, and the separation is
+/// the span multiplied by the fraction, rounded to the nearest integer. If the span is zero,
+/// including when all the preferred positions are equal or there are fewer than two labels, the
+/// separation is zero and the labels are left at their preferred positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 1, 3, 100];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_relative(&preferred_positions, 0.1);
+///
+/// assert_eq!([-9, 1, 11, 100], *permitted_positions);
+/// # }
+/// ```
+pub fn place_relative(positions: &[i32], separation_fraction: f64) -> Vec<i32> {
+    let span = match (positions.first(), positions.last()) {
+        (Some(first), Some(last)) => f64::from(*last) - f64::from(*first),
+        _ => 0.0,
+    };
+
+    place(positions, (span * separation_fraction).round() as i32)
+}
+
+/// Places labels, respecting a minimum separation, laying out the labels within each cluster using
+/// a custom layout function.
+///
+/// The labels are grouped into clusters as by `place()`, and then each label is placed at
+/// `start + layout_member(index)`, where `start` is the start position of its cluster and `index`
+/// is its index within the cluster. A layout of `|index| separation * index as i32` gives the same
+/// result as `place()`. Other layouts may violate the minimum separation.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10, 100];
+///
+/// let permitted_positions = vertical_label_placement::place_with_layout(
+///     &preferred_positions,
+///     10,
+///     |index| [0, 10, 25, 30][index]
+/// );
+///
+/// assert_eq!([-15, -5, 10, 15, 100], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_layout(
+    positions: &[i32],
+    separation: i32,
+    layout_member: impl Fn(usize) -> i32,
+) -> Vec<i32> {
+    let mut placed = Vec::with_capacity(positions.len());
+
+    for cluster in cluster(positions, separation).vec {
+        placed.extend((0..cluster.len).map(|index| cluster.start + layout_member(index)));
+    }
+
+    placed
+}
+
+/// Places labels whose preferred positions are already sorted, respecting a minimum separation.
+///
+/// The preferred positions must be sorted in ascending order. This is checked only by a debug
+/// assertion, and unlike `place()`, there is no initial check for whether the labels are already
+/// separated, so the labels are always grouped into clusters directly.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_presorted(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_presorted(positions: &[i32], separation: i32) -> Vec<i32> {
+    debug_assert!(positions.is_sorted(), "positions are not sorted");
+
+    cluster(positions, separation).positions()
+}
+
+/// Places labels stacked in descending order, permitting each label to lie no more than a fixed
+/// overlap below the previous label.
+///
+/// Unlike the other functions, the preferred positions must be sorted in descending order, as
+/// when labels are stacked in layers. Wherever neighbouring labels descend by more than the
+/// overlap, they are pulled together so that each permitted position is at least
+/// `previous - overlap`, and the labels pulled together are balanced to minimise the maximum
+/// absolute offset of any label from its preferred position.
+///
+/// # Panics
+///
+/// Panics if `overlap` is negative.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![10, 0, -10];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_overlapping(&preferred_positions, 5);
+///
+/// assert_eq!([5, 0, -5], *permitted_positions);
+/// # }
+/// ```
+pub fn place_overlapping(positions: &[i32], overlap: i32) -> Vec<i32> {
+    assert!(overlap >= 0, "overlap is negative");
+    debug_assert!(
+        positions.windows(2).all(|pair| pair[0] >= pair[1]),
+        "positions are not sorted in descending order"
+    );
+
+    place_with_separations(positions, |_| -overlap, |_| (i32::MIN, i32::MAX))
+}
+
+/// Places labels with `i16` positions, respecting a minimum separation.
+///
+/// The positions are widened to `i32` and placed as by `place()`, so intermediate values cannot
+/// overflow while the number of labels multiplied by the separation is at most `i32::MAX / 2`.
+/// Permitted positions beyond the range of `i16` are saturated to `i16::MIN` or `i16::MAX`, in
+/// which case the minimum separation is not respected for them.
+///
+/// # Panics
+///
+/// Panics if the number of labels multiplied by the separation exceeds `i32::MAX / 2`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_i16(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// # }
+/// ```
+pub fn place_i16(positions: &[i16], separation: i16) -> Vec<i16> {
+    assert!(
+        positions.len() as u64 * u64::from(separation.unsigned_abs()) <= i32::MAX as u64 / 2,
+        "labels span too far to place"
+    );
+
+    let widened: Vec<i32> = positions
+        .iter()
+        .map(|position| i32::from(*position))
+        .collect();
+
+    place(&widened, i32::from(separation))
+        .into_iter()
+        .map(|position| position.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16)
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, returning the sequence of cluster merges
+/// alongside the permitted positions.
+///
+/// The merges are listed in the order in which they were performed. Each records the start and
+/// end positions of the two clusters before merging and of the merged cluster.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::MergeEvent;
+///
+/// let preferred_positions = vec![0, 0, 100];
+///
+/// let (permitted_positions, trace) =
+///     vertical_label_placement::place_with_trace(&preferred_positions, 10);
+///
+/// assert_eq!([-5, 5, 100], *permitted_positions);
+/// assert_eq!(
+///     [MergeEvent {
+///         first: (0, 0),
+///         second: (0, 0),
+///         merged: (-5, 5)
+///     }],
+///     *trace
+/// );
+/// # }
+/// ```
+pub fn place_with_trace(positions: &[i32], separation: i32) -> (Vec<i32>, Vec<MergeEvent>) {
+    let mut clusters = ClusterList::new(separation, positions.len());
+    let mut trace = Vec::new();
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            let merged = Cluster::merge(previous, cluster, separation);
+
+            trace.push(MergeEvent {
+                first: (previous.start, previous.end),
+                second: (cluster.start, cluster.end),
+                merged: (merged.start, merged.end),
+            });
+
+            cluster = merged;
+        }
+
+        clusters.push(cluster);
+    }
+
+    (clusters.positions(), trace)
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, dropping the
+/// labels with the lowest priorities that do not fit within the limits, but keeping at least a
+/// minimum number of labels.
+///
+/// As many labels as fit within the limits are kept, but no fewer than `keep` (or all the labels,
+/// if there are fewer). Labels with higher priorities are kept in preference to labels with lower
+/// priorities, and labels with equal priorities are kept in input order. The permitted position of
+/// each kept label is returned, and `None` for each dropped label. If the kept labels do not fit
+/// within the limits, they overflow as for `place_with_limits()`.
+///
+/// # Panics
+///
+/// Panics if `priorities` is not the same length as `positions`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 0, 0, 0];
+///
+/// let permitted_positions = vertical_label_placement::place_keep_at_least(
+///     &preferred_positions,
+///     10,
+///     0,
+///     10,
+///     1,
+///     &[1, 4, 3, 2]
+/// );
+///
+/// assert_eq!([None, Some(0), Some(10), None], *permitted_positions);
+/// # }
+/// ```
+pub fn place_keep_at_least(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    keep: usize,
+    priorities: &[u32],
+) -> Vec<Option<i32>> {
+    assert_eq!(
+        positions.len(),
+        priorities.len(),
+        "priority count does not match"
+    );
+
+    let capacity = match separation {
+        separation if separation > 0 => {
+            ((i64::from(max) - i64::from(min)) / i64::from(separation) + 1).max(0) as usize
+        }
+        _ => positions.len(),
+    };
+
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by_key(|&index| Reverse(priorities[index]));
+    order.truncate(capacity.max(keep));
+    order.sort_unstable();
+
+    let kept: Vec<i32> = order.iter().map(|&index| positions[index]).collect();
+
+    let mut placed = vec![None; positions.len()];
+    for (index, position) in order
+        .into_iter()
+        .zip(place_with_limits(&kept, separation, min, max))
+    {
+        placed[index] = Some(position);
+    }
+
+    placed
+}
+
+/// Places labels attached to data points, respecting a minimum separation, returning the
+/// connector from each data point to its label.
+///
+/// The data points are the preferred positions, so they must be sorted in ascending order.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Connector;
+///
+/// let connectors = vertical_label_placement::place_connectors(&[0, 0], 10);
+///
+/// assert_eq!(
+///     [
+///         Connector {
+///             point: 0,
+///             label_position: -5,
+///             offset: -5
+///         },
+///         Connector {
+///             point: 0,
+///             label_position: 5,
+///             offset: 5
+///         }
+///     ],
+///     *connectors
+/// );
+/// # }
+/// ```
+pub fn place_connectors(points: &[i32], separation: i32) -> Vec<Connector> {
+    cluster(points, separation)
+        .into_positions()
+        .zip(points)
+        .map(|(label_position, point)| Connector {
+            point: *point,
+            label_position,
+            offset: label_position - point,
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, splitting
+/// any overflow between the limits if they do not provide sufficient space for all the labels.
+///
+/// If a cluster does not fit within the limits, it is placed so that `upward_fraction` of the
+/// overflow lies beyond the maximum limit and the rest beyond the minimum limit, rounding the
+/// upward overflow to the nearest integer. An upward fraction of zero gives the same result as
+/// `place_with_limits()`, and an upward fraction of one respects only the minimum limit.
+///
+/// # Panics
+///
+/// Panics if `upward_fraction` is not between zero and one.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::place_with_limits_split(
+///     &preferred_positions,
+///     10,
+///     -10,
+///     10,
+///     0.3
+/// );
+///
+/// assert_eq!([-17, -7, 3, 13], *permitted_positions);
+/// # }
+/// ```
+pub fn place_with_limits_split(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+    upward_fraction: f64,
+) -> Vec<i32> {
+    assert!(
+        (0.0..=1.0).contains(&upward_fraction),
+        "upward fraction is not between zero and one"
+    );
+
+    let limit = |mut cluster: Cluster| {
+        let overflow =
+            (i64::from(cluster.end) - i64::from(cluster.start)) - (i64::from(max) - i64::from(min));
+
+        if overflow > 0 {
+            let upward = (overflow as f64 * upward_fraction).round() as i64;
+            cluster.shift((i64::from(max) + upward - i64::from(cluster.end)) as i32);
+            cluster
+        } else {
+            cluster.limit(min, max)
+        }
+    };
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = limit(Cluster::new(*position));
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = limit(Cluster::merge(previous, cluster, separation));
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, returning
+/// how each label was affected by the limits alongside its permitted position.
+///
+/// A label is `LimitStatus::Overflowed` if its permitted position lies beyond the limits, which
+/// only happens if the limits do not provide sufficient space for all the labels, and
+/// `LimitStatus::Clamped` if it was moved to a limit that it would otherwise lie beyond. Otherwise
+/// it is `LimitStatus::Placed`, even if its cluster was moved by the limits.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::LimitStatus;
+///
+/// let preferred_positions = vec![0, 0, 100];
+///
+/// let statuses =
+///     vertical_label_placement::place_with_limits_status(&preferred_positions, 10, 0, 90);
+///
+/// assert_eq!(
+///     [
+///         (0, LimitStatus::Clamped),
+///         (10, LimitStatus::Placed),
+///         (90, LimitStatus::Clamped)
+///     ],
+///     *statuses
+/// );
+/// # }
+/// ```
+pub fn place_with_limits_status(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> Vec<(i32, LimitStatus)> {
+    place_with_limits(positions, separation, min, max)
+        .into_iter()
+        .zip(cluster(positions, separation).into_positions())
+        .map(|(position, unlimited)| {
+            let status = if position < min || position > max {
+                LimitStatus::Overflowed
+            } else if (position == min && unlimited < min) || (position == max && unlimited > max) {
+                LimitStatus::Clamped
+            } else {
+                LimitStatus::Placed
+            };
+
+            (position, status)
+        })
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, returning
+/// the unused space below the first label and above the last label alongside the permitted
+/// positions.
+///
+/// The unused space is `first - min` below and `max - last` above, saturating at the bounds of
+/// `i32`. It is negative beyond a limit that the labels overflow. If there are no labels, both are
+/// `max - min`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let (permitted_positions, below, above) =
+///     vertical_label_placement::place_with_limits_slack(&preferred_positions, 10, -20, 100);
+///
+/// assert_eq!([-15, -5, 5, 15], *permitted_positions);
+/// assert_eq!((5, 85), (below, above));
+/// # }
+/// ```
+pub fn place_with_limits_slack(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> (Vec<i32>, i32, i32) {
+    let placed = place_with_limits(positions, separation, min, max);
+
+    let first = placed.first().copied().unwrap_or(max);
+    let last = placed.last().copied().unwrap_or(min);
+
+    (placed, first.saturating_sub(min), max.saturating_sub(last))
+}
+
+/// Places labels on two axes sharing the same space, respecting a minimum separation and minimum
+/// and maximum positions independently on each axis.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let (left, right) = vertical_label_placement::place_dual(&[0, 20], &[0, 0], 10, 0, 100);
+///
+/// assert_eq!([0, 20], *left);
+/// assert_eq!([0, 10], *right);
+/// # }
+/// ```
+pub fn place_dual(
+    left: &[i32],
+    right: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> (Vec<i32>, Vec<i32>) {
+    (
+        place_with_limits(left, separation, min, max),
+        place_with_limits(right, separation, min, max),
+    )
+}
+
+/// Places labels whose preferred positions are not necessarily sorted, respecting a minimum
+/// separation, returning the permitted positions in input order alongside the permutation that
+/// sorts the labels.
+///
+/// The permutation lists the indices of the labels in ascending order of preferred position, with
+/// labels with equal preferred positions in input order, so `permutation[i]` is the index of the
+/// `i`th lowest label. It can be used to sort parallel arrays in the same way.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![10, -10, 1, -1];
+///
+/// let (permitted_positions, permutation) =
+///     vertical_label_placement::place_unsorted_with_permutation(&preferred_positions, 10);
+///
+/// assert_eq!([15, -15, 5, -5], *permitted_positions);
+/// assert_eq!([1, 3, 2, 0], *permutation);
+/// # }
+/// ```
+pub fn place_unsorted_with_permutation(
+    positions: &[i32],
+    separation: i32,
+) -> (Vec<i32>, Vec<usize>) {
+    let mut permutation: Vec<usize> = (0..positions.len()).collect();
+    permutation.sort_by_key(|&index| positions[index]);
+
+    let sorted: Vec<i32> = permutation.iter().map(|&index| positions[index]).collect();
+
+    let mut placed = vec![0; positions.len()];
+    for (index, position) in permutation
+        .iter()
+        .zip(cluster(&sorted, separation).into_positions())
+    {
+        placed[*index] = position;
+    }
+
+    (placed, permutation)
+}
+
+/// Places labels, respecting a minimum separation, such that no label lies within a clearance of
+/// any marker.
+///
+/// Each marker forbids the positions less than `clearance` from it, and markers whose forbidden
+/// zones overlap or touch form a single zone. As for `place_avoiding_zone()`, the labels are first
+/// placed as by `place()`, each cluster that intersects a zone is assigned to whichever side of
+/// the zone it is nearer, and the labels between each pair of zones are placed again with the
+/// zones as limits. If the labels assigned to the space between two zones do not fit within it,
+/// only the upper zone is respected for them.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 18, 40];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_clear_of(&preferred_positions, 10, &[20, 35], 5);
+///
+/// assert_eq!([0, 15, 40], *permitted_positions);
+/// # }
+/// ```
+pub fn place_clear_of(
+    positions: &[i32],
+    separation: i32,
+    markers: &[i32],
+    clearance: i32,
+) -> Vec<i32> {
+    if clearance <= 0 {
+        return place(positions, separation);
+    }
+
+    let mut markers = markers.to_vec();
+    markers.sort_unstable();
+
+    let mut zones: Vec<(i64, i64)> = Vec::with_capacity(markers.len());
+
+    for marker in markers {
+        let start = i64::from(marker) - i64::from(clearance) + 1;
+        let end = i64::from(marker) + i64::from(clearance) - 1;
+
+        match zones.last_mut() {
+            Some(last) if last.1 + 1 >= start => last.1 = end,
+            _ => zones.push((start, end)),
+        }
+    }
+
+    let mut gaps = vec![0; zones.len() + 1];
+
+    for cluster in &cluster(positions, separation).vec {
+        let index = zones.partition_point(|zone| zone.1 < i64::from(cluster.start));
+
+        let gap = match zones.get(index) {
+            Some(&(start, end)) if start <= i64::from(cluster.end) => {
+                let below = i64::from(cluster.end) - start + 1;
+                let above = end + 1 - i64::from(cluster.start);
+
+                if below <= above {
+                    index
+                } else {
+                    index + 1
+                }
+            }
+            _ => index,
+        };
+
+        gaps[gap] += cluster.len;
+    }
+
+    let clamp = |position: i64| position.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+
+    let mut placed: Vec<i32> = Vec::with_capacity(positions.len());
+
+    for (index, len) in gaps.into_iter().enumerate() {
+        let first = placed.len();
+
+        let mut min = match index.checked_sub(1) {
+            Some(previous) => clamp(zones[previous].1 + 1),
+            None => i32::MIN,
+        };
+
+        let max = match zones.get(index) {
+            Some(zone) => clamp(zone.0 - 1),
+            None => i32::MAX,
+        };
+
+        if let Some(last) = placed.last() {
+            min = min.max(last.saturating_add(separation));
+        }
+
+        placed.extend(place_with_limits(
+            &positions[first..first + len],
+            separation,
+            min,
+            max,
+        ));
+    }
+
+    placed
+}
+
+/// Places labels, respecting a minimum separation, keeping each label at a previous permitted
+/// position unless its new permitted position differs from it by more than a band.
+///
+/// The labels are first placed as by `place()`. Each label whose permitted position is within
+/// `band` of its previous position is then moved back to its previous position, which prevents
+/// labels flickering between nearby positions when the preferred positions change slightly. If
+/// this leaves any labels closer than the minimum separation, they are moved apart as by
+/// `repair()`.
+///
+/// # Panics
+///
+/// Panics if the number of previous positions does not match the number of preferred positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let previous_positions = vertical_label_placement::place(&[-10, -1, 1, 10], 10);
+///
+/// let permitted_positions = vertical_label_placement::place_with_hysteresis(
+///     &[-10, -1, 2, 10],
+///     10,
+///     &previous_positions,
+///     2
+/// );
+///
+/// assert_eq!(previous_positions, permitted_positions);
+/// # }
+/// ```
+pub fn place_with_hysteresis(
+    positions: &[i32],
+    separation: i32,
+    previous: &[i32],
+    band: i32,
+) -> Vec<i32> {
+    assert_eq!(
+        positions.len(),
+        previous.len(),
+        "previous count does not match"
+    );
+
+    let mut placed = place(positions, separation);
+
+    for (position, previous) in placed.iter_mut().zip(previous) {
+        if (i64::from(*position) - i64::from(*previous)).abs() <= i64::from(band) {
+            *position = *previous;
+        }
+    }
+
+    repair(&mut placed, separation);
+    placed
+}
+
+/// Places labels, respecting a minimum separation, returning a bitset of the labels moved from
+/// their preferred positions alongside the permitted positions.
+///
+/// Bit `i % 64` of element `i / 64` of the bitset is set if the label at index `i` was moved, so
+/// `mask[i / 64] >> (i % 64) & 1` is one for each moved label. The bitset has one element for each
+/// 64 labels, rounding up.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![-10, 0, 0, 100];
+///
+/// let (permitted_positions, mask) =
+///     vertical_label_placement::place_with_moved_mask(&preferred_positions, 10);
+///
+/// assert_eq!([-15, -5, 5, 100], *permitted_positions);
+/// assert_eq!([0b0111], *mask);
+/// # }
+/// ```
+pub fn place_with_moved_mask(positions: &[i32], separation: i32) -> (Vec<i32>, Vec<u64>) {
+    let mut placed = Vec::with_capacity(positions.len());
+    let mut mask = vec![0; positions.len().div_ceil(64)];
+
+    for (index, (position, preferred)) in cluster(positions, separation)
+        .into_positions()
+        .zip(positions)
+        .enumerate()
+    {
+        if position != *preferred {
+            mask[index / 64] |= 1 << (index % 64);
+        }
+
+        placed.push(position);
+    }
+
+    (placed, mask)
+}
+
+/// Places labels, respecting a minimum separation, after thinning them to a maximum density,
+/// returning the index and permitted position of each kept label.
+///
+/// The density is the number of labels divided by the span from the first preferred position to
+/// the last. If it exceeds `max_density`, only every `k`th label is kept, starting with the first,
+/// where `k` is the ratio of the density to `max_density` rounded up. If the span is zero, only the
+/// first label is kept.
+///
+/// # Panics
+///
+/// Panics if `max_density` is not positive.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 1, 2, 3, 4, 5];
+///
+/// let kept = vertical_label_placement::place_thinned(&preferred_positions, 2, 0.5);
+///
+/// assert_eq!([(0, 0), (3, 3)], *kept);
+/// # }
+/// ```
+pub fn place_thinned(positions: &[i32], separation: i32, max_density: f64) -> Vec<(usize, i32)> {
+    assert!(max_density > 0.0, "maximum density is not positive");
+
+    let span = match (positions.first(), positions.last()) {
+        (Some(first), Some(last)) => f64::from(*last) - f64::from(*first),
+        _ => 0.0,
+    };
+
+    let stride = match positions.len() {
+        0 | 1 => 1,
+        len if span == 0.0 => len,
+        len => ((len as f64 / span / max_density).ceil() as usize).max(1),
+    };
+
+    let kept: Vec<i32> = positions.iter().copied().step_by(stride).collect();
+
+    (0..positions.len())
+        .step_by(stride)
+        .zip(cluster(&kept, separation).into_positions())
+        .collect()
+}
+
+/// Places labels with heights from an iterator of `(center, height)` pairs, respecting a minimum
+/// gap between neighbouring labels.
+///
+/// The labels must be sorted in ascending order of preferred center. Each label is separated from
+/// the previous label by their half-heights plus the gap, as in `place_sized_with_limits()`, so
+/// only the height of the previous label is needed and the labels are consumed in a single pass.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let labels = vec![(0, 10), (0, 30)];
+///
+/// let permitted_centers = vertical_label_placement::place_sized_from_iter(labels, 0);
+///
+/// assert_eq!([-10, 10], *permitted_centers);
+/// # }
+/// ```
+pub fn place_sized_from_iter<I: IntoIterator<Item = (i32, i32)>>(labels: I, gap: i32) -> Vec<i32> {
+    let labels = labels.into_iter();
+    let mut offsets = Vec::with_capacity(labels.size_hint().0);
+    let mut clusters = ClusterList::new(0, labels.size_hint().0);
+    let mut previous = None;
+
+    for (center, height) in labels {
+        let offset = match previous {
+            Some((offset, previous_height)) => {
+                offset + previous_height - previous_height / 2 + height / 2 + gap
+            }
+            None => 0,
+        };
+        offsets.push(offset);
+        previous = Some((offset, height));
+
+        let mut cluster = Cluster::new(center - offset);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, 0);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters
+        .into_positions()
+        .zip(offsets)
+        .map(|(position, offset)| position + offset)
+        .collect()
+}
+
+/// Places labels, respecting a minimum separation, minimising the maximum absolute offset of any
+/// label within each cluster and then, among the equally good placements, the total absolute
+/// offset.
+///
+/// When the labels in a cluster cannot be balanced exactly, two start positions give the same
+/// maximum absolute offset. Whereas `place()` chooses the one requiring the smaller shift of the
+/// cluster, this function chooses the one that moves the labels less in total. Each merge
+/// evaluates the offsets of every label in the merged cluster, so this is slower than `place()`
+/// for large clusters.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// let preferred_positions = vec![0, 1, 1, 7];
+///
+/// let permitted_positions =
+///     vertical_label_placement::place_lexicographic(&preferred_positions, 3);
+///
+/// assert_eq!([-2, 1, 4, 7], *permitted_positions);
+/// # }
+/// ```
+pub fn place_lexicographic(positions: &[i32], separation: i32) -> Vec<i32> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::join(previous, cluster, separation);
+
+            let first = index + 1 - cluster.len;
+            let start = lexicographic_start(&positions[first..=index], separation, cluster.start);
+
+            cluster.shift(start - cluster.start);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters.positions()
+}
+
+/// Places labels, respecting a minimum separation, after validating the separation.
+///
+/// With the `checked` feature enabled, arithmetic overflow is detected and reported as an error,
+/// rather than panicking or wrapping.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::PlacementError;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions = vertical_label_placement::try_place(&preferred_positions, 10);
+/// let invalid_positions = vertical_label_placement::try_place(&preferred_positions, -10);
+///
+/// assert_eq!(Ok(vec![-15, -5, 5, 15]), permitted_positions);
+/// assert_eq!(Err(PlacementError::NegativeSeparation), invalid_positions);
+/// # }
+/// ```
+pub fn try_place(positions: &[i32], separation: i32) -> Result<Vec<i32>, PlacementError> {
+    validate(separation, None)?;
+
+    #[cfg(feature = "checked")]
+    return checked_cluster(positions, separation)
+        .and_then(ClusterList::checked_positions)
+        .ok_or(PlacementError::Overflow);
+
+    #[cfg(not(feature = "checked"))]
+    Ok(place(positions, separation))
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, after
+/// validating the separation and limits.
+///
+/// If the limits do not provide sufficient space for all the labels, an error is returned before
+/// any labels are placed.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::PlacementError;
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// let permitted_positions =
+///     vertical_label_placement::try_place_with_limits(&preferred_positions, 10, 0, 100);
+/// let invalid_positions =
+///     vertical_label_placement::try_place_with_limits(&preferred_positions, 10, 100, 0);
+/// let infeasible_positions =
+///     vertical_label_placement::try_place_with_limits(&preferred_positions, 10, 0, 20);
+///
+/// assert_eq!(Ok(vec![0, 10, 20, 30]), permitted_positions);
+/// assert_eq!(Err(PlacementError::InvalidLimits), invalid_positions);
+/// assert_eq!(Err(PlacementError::Infeasible), infeasible_positions);
+/// # }
+/// ```
+pub fn try_place_with_limits(
+    positions: &[i32],
+    separation: i32,
+    min: i32,
+    max: i32,
+) -> Result<Vec<i32>, PlacementError> {
+    validate(separation, Some((min, max)))?;
+
+    let required = (positions.len().saturating_sub(1) as u64).checked_mul(separation as u64);
+
+    if required.is_none_or(|required| required > (i64::from(max) - i64::from(min)) as u64) {
+        return Err(PlacementError::Infeasible);
+    }
+
+    Ok(place_with_limits(positions, separation, min, max))
+}
+
+/// Places labels, respecting a minimum separation, after validating the separation, performing no
+/// more than a maximum number of cluster merges.
+///
+/// Each merge reduces the number of clusters by one, so placing `n` labels never takes more than
+/// `n - 1` merges.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::{try_place_bounded, PlacementError};
+///
+/// let preferred_positions = vec![-10, -1, 1, 10];
+///
+/// assert_eq!(Ok(vec![-15, -5, 5, 15]), try_place_bounded(&preferred_positions, 10, 3));
+/// assert_eq!(Err(PlacementError::Budget), try_place_bounded(&preferred_positions, 10, 2));
+/// # }
+/// ```
+pub fn try_place_bounded(
+    positions: &[i32],
+    separation: i32,
+    max_ops: usize,
+) -> Result<Vec<i32>, PlacementError> {
+    validate(separation, None)?;
+
+    let mut clusters = ClusterList::new(separation, positions.len());
+    let mut ops = 0;
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            ops += 1;
+
+            if ops > max_ops {
+                return Err(PlacementError::Budget);
+            }
+
+            cluster = Cluster::merge(previous, cluster, separation);
+        }
+
+        clusters.push(cluster);
+    }
+
+    Ok(clusters.positions())
+}
+
+/// Validates a minimum separation and optional minimum and maximum positions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::{validate, PlacementError};
+///
+/// assert_eq!(Ok(()), validate(10, Some((0, 100))));
+/// assert_eq!(Err(PlacementError::NegativeSeparation), validate(-10, None));
+/// assert_eq!(Err(PlacementError::InvalidLimits), validate(10, Some((100, 0))));
+/// # }
+/// ```
+pub fn validate(separation: i32, limits: Option<(i32, i32)>) -> Result<(), PlacementError> {
+    if separation < 0 {
+        return Err(PlacementError::NegativeSeparation);
+    }
+
+    if let Some((min, max)) = limits {
+        if min > max {
+            return Err(PlacementError::InvalidLimits);
+        }
+    }
+
+    Ok(())
+}
+
+/// Represents a reason why labels cannot be placed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlacementError {
+    /// The minimum separation is negative.
+    NegativeSeparation,
+    /// The minimum position is greater than the maximum position.
+    InvalidLimits,
+    /// The permitted positions cannot be represented as `i32` values. This is only detected with
+    /// the `checked` feature enabled.
+    Overflow,
+    /// The limits do not provide sufficient space for all the labels.
+    Infeasible,
+    /// A label would be offset from its preferred position by more than the permitted maximum.
+    OffsetCapExceeded,
+    /// The placement would perform more than the permitted number of operations.
+    Budget,
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NegativeSeparation => "the minimum separation is negative",
+            Self::InvalidLimits => "the minimum position is greater than the maximum position",
+            Self::Overflow => "the permitted positions overflow the range of i32",
+            Self::Infeasible => "the limits do not provide sufficient space for the labels",
+            Self::OffsetCapExceeded => "a label exceeds the maximum permitted offset",
+            Self::Budget => "the placement exceeds the permitted number of operations",
+        })
+    }
+}
+
+impl Error for PlacementError {}
+
+/// Represents statistics about a placement.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of times two clusters were merged.
+    pub merges: usize,
+    /// The number of labels in the largest cluster.
+    pub max_cluster_size: usize,
+    /// The number of clusters.
+    pub cluster_count: usize,
+}
+
+/// Represents metrics describing a placement.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The maximum absolute offset of any label from its preferred position.
+    pub max_offset: u64,
+    /// The sum of the absolute offsets of the labels from their preferred positions.
+    pub total_displacement: u64,
+    /// The number of clusters.
+    pub cluster_count: usize,
+    /// The number of labels in the largest cluster.
+    pub max_cluster_size: usize,
+}
+
+/// Represents how labels are placed if the limits do not provide sufficient space for them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Only the maximum limit is respected, as for `place_with_limits()`.
+    #[default]
+    Max,
+    /// The labels are centred between the limits, overflowing equally beyond both.
+    Centre,
+    /// The labels beyond the limits are moved to the limits, so the minimum separation is not
+    /// respected for them.
+    Clamp,
+    /// The labels are spaced evenly from the minimum limit to the maximum limit, so the minimum
+    /// separation is not respected for any of them.
+    Compress,
+}
+
+/// Represents how a label was affected by the limits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LimitStatus {
+    /// The label lies within the limits without being moved to one of them.
+    Placed,
+    /// The label was moved to one of the limits, which it would otherwise lie beyond.
+    Clamped,
+    /// The label lies beyond the limits.
+    Overflowed,
+}
+
+/// Represents minimum and maximum positions within which labels are placed.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Placement;
+///
+/// let placement = Placement::for_container(100, 10).unwrap();
+///
+/// let permitted_positions = placement.place(&[-10, -1, 1, 10], 10);
+///
+/// assert_eq!([10, 20, 30, 40], *permitted_positions);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    /// The minimum position.
+    min: i32,
+    /// The maximum position.
+    max: i32,
+}
+
+impl Placement {
+    /// Creates limits with the specified minimum and maximum positions.
+    pub fn new(min: i32, max: i32) -> Result<Self, PlacementError> {
+        if min > max {
+            return Err(PlacementError::InvalidLimits);
+        }
+
+        Ok(Self { min, max })
+    }
+
+    /// Creates limits for a container of the specified height, keeping labels at least the
+    /// specified margin from its edges.
+    ///
+    /// The minimum position is `margin` and the maximum position is `height - margin`. If the
+    /// maximum position overflows, the limits are invalid.
+    pub fn for_container(height: i32, margin: i32) -> Result<Self, PlacementError> {
+        let max = height
+            .checked_sub(margin)
+            .ok_or(PlacementError::InvalidLimits)?;
+
+        Self::new(margin, max)
+    }
+
+    /// Returns the minimum position.
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    /// Returns the maximum position.
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    /// Places labels, respecting a minimum separation and the limits.
+    pub fn place(&self, positions: &[i32], separation: i32) -> Vec<i32> {
+        place_with_limits(positions, separation, self.min, self.max)
+    }
+}
+
+/// Represents how floating-point positions are rounded to integers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds towards positive infinity.
+    Ceil,
+    /// Rounds to the nearest integer, rounding halfway cases towards positive infinity.
+    HalfUp,
+    /// Rounds to the nearest integer, rounding halfway cases to the nearest even integer.
+    HalfEven,
+}
+
+impl Rounding {
+    /// Rounds a floating-point position to an integer.
+    fn round(self, position: f64) -> i32 {
+        let rounded = match self {
+            Self::Floor => position.floor(),
+            Self::Ceil => position.ceil(),
+            Self::HalfUp => (position + 0.5).floor(),
+            Self::HalfEven => position.round_ties_even(),
+        };
+
+        rounded as i32
+    }
+}
+
+/// Represents how the shift that balances a cluster is rounded when the labels cannot be balanced
+/// exactly.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RoundMode {
+    /// Rounds towards zero, as for `place()`.
+    #[default]
+    TowardZero,
+    /// Rounds towards negative infinity.
+    TowardNegInf,
+    /// Rounds to the nearest integer, rounding halfway cases to the nearest even integer.
+    Nearest,
+}
+
+impl RoundMode {
+    /// Halves a value, rounding the result.
+    fn halve(self, value: i32) -> i32 {
+        let floor = value.div_euclid(2);
+
+        match self {
+            Self::TowardZero => value / 2,
+            Self::TowardNegInf => floor,
+            Self::Nearest if value % 2 != 0 && floor % 2 != 0 => floor + 1,
+            Self::Nearest => floor,
+        }
+    }
+}
+
+/// Places labels incrementally as they are inserted and removed, respecting a minimum separation.
+///
+/// After each change, only the clusters from the one containing the changed label onwards are
+/// placed again, as earlier clusters cannot be affected.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::IncrementalPlacer;
+///
+/// let mut placer = IncrementalPlacer::new(10);
+///
+/// for position in [-10, -1, 1, 10] {
+///     placer.insert(position);
+/// }
+///
+/// assert_eq!([-15, -5, 5, 15], *placer.positions());
+///
+/// placer.remove(1);
+///
+/// assert_eq!([-10, 0, 10], *placer.positions());
+/// # }
+/// ```
+pub struct IncrementalPlacer {
+    /// The sorted preferred positions.
+    preferred: Vec<i32>,
+    /// The clusters of labels.
+    clusters: ClusterList,
+}
+
+impl IncrementalPlacer {
+    /// Creates a new placer with no labels.
+    pub fn new(separation: i32) -> Self {
+        Self {
+            preferred: Vec::new(),
+            clusters: ClusterList::new(separation, 0),
+        }
+    }
+
+    /// Inserts a label with the specified preferred position, returning its index among the
+    /// labels sorted by preferred position.
+    pub fn insert(&mut self, position: i32) -> usize {
+        let index = self
+            .preferred
+            .partition_point(|preferred| *preferred <= position);
+        self.preferred.insert(index, position);
+        self.place_from(index);
+        index
+    }
+
+    /// Appends a label with the specified preferred position.
+    ///
+    /// A label at or above every existing preferred position can only merge with the clusters at
+    /// the end, so appending takes amortised constant time. A label below an existing preferred
+    /// position is instead inserted as by `insert()`.
+    pub fn push(&mut self, position: i32) {
+        if self.preferred.last().is_some_and(|last| *last > position) {
+            self.insert(position);
+            return;
+        }
+
+        self.preferred.push(position);
+
+        let mut cluster = Cluster::new(position);
+
+        while let Some(previous) = self.clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, self.clusters.separation);
+        }
+
+        self.clusters.push(cluster);
+    }
+
+    /// Removes the label at the specified index, returning its preferred position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> i32 {
+        let position = self.preferred.remove(index);
+        self.place_from(index);
+        position
+    }
+
+    /// Returns the permitted positions.
+    pub fn positions(&self) -> Vec<i32> {
+        let separation = self.clusters.separation;
+
+        self.clusters
+            .vec
+            .iter()
+            .flat_map(|cluster| cluster.positions(separation))
+            .collect()
+    }
+
+    /// Places the labels again from the start of the cluster containing the label at the
+    /// specified index.
+    ///
+    /// Clusters that were never merged with later labels are unaffected by changes to those
+    /// labels, so the clusters before the one containing the label can be kept. Likewise, the
+    /// clusters after the label were never merged with earlier labels, so once the labels have
+    /// been placed again up to the start of one of those clusters, with the last label placed no
+    /// higher than before, the remaining clusters can be kept. A change that moves the labels
+    /// above it upwards still places the labels again to the end, taking time proportional to the
+    /// number of labels after it.
+    fn place_from(&mut self, index: usize) {
+        let mut first = 0;
+        let mut kept = 0;
+
+        while let Some(cluster) = self.clusters.vec.get(kept) {
+            if first + cluster.len > index {
+                break;
+            }
+
+            first += cluster.len;
+            kept += 1;
+        }
+
+        let previous_clusters = self.clusters.vec.split_off(kept);
+        let previous_len = first
+            + previous_clusters
+                .iter()
+                .map(|cluster| cluster.len)
+                .sum::<usize>();
+
+        let separation = self.clusters.separation;
+        let mut matched = 0;
+        let mut previous_end = first;
+
+        for (end, position) in (first + 1..).zip(&self.preferred[first..]) {
+            let mut cluster = Cluster::new(*position);
+
+            while let Some(previous) = self.clusters.pop_if_not_separate(cluster) {
+                cluster = Cluster::merge(previous, cluster, separation);
+            }
+
+            self.clusters.push(cluster);
+
+            if end <= index {
+                continue;
+            }
+
+            let previous_boundary = end + previous_len - self.preferred.len();
+
+            while matched < previous_clusters.len() && previous_end < previous_boundary {
+                previous_end += previous_clusters[matched].len;
+                matched += 1;
+            }
+
+            if previous_end == previous_boundary
+                && matched > 0
+                && cluster.end <= previous_clusters[matched - 1].end
+            {
+                self.clusters
+                    .vec
+                    .extend_from_slice(&previous_clusters[matched..]);
+                return;
+            }
+        }
+    }
+}
+
+/// Places labels, respecting a minimum separation and minimum and maximum positions, caching the
+/// clusters placed without limits so that the limits can be changed cheaply.
+///
+/// Changing a limit places the cached clusters again, rather than the individual labels, merging
+/// them only where the limits move them too close together. This takes time proportional to the
+/// number of clusters rather than the number of labels.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() {
+/// use vertical_label_placement::Layout;
+///
+/// let mut layout = Layout::new(&[-10, -1, 1, 10], 10, -100, 100);
+///
+/// assert_eq!([-15, -5, 5, 15], *layout.positions());
+///
+/// layout.set_min(0);
+///
+/// assert_eq!([0, 10, 20, 30], *layout.positions());
+/// # }
+/// ```
+pub struct Layout {
+    /// The clusters placed without limits.
+    unlimited: Vec<Cluster>,
+    /// The clusters placed with limits.
+    clusters: ClusterList,
+    /// The minimum position.
+    min: i32,
+    /// The maximum position.
+    max: i32,
+}
+
+impl Layout {
+    /// Creates a new layout for labels with the specified preferred positions.
+    pub fn new(positions: &[i32], separation: i32, min: i32, max: i32) -> Self {
+        let unlimited = cluster(positions, separation).vec;
+
+        let mut layout = Self {
+            clusters: ClusterList::new(separation, unlimited.len()),
+            unlimited,
+            min,
+            max,
+        };
+
+        layout.place();
+        layout
+    }
+
+    /// Returns the minimum position.
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    /// Returns the maximum position.
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    /// Sets the minimum position and places the labels again.
+    pub fn set_min(&mut self, min: i32) {
+        self.min = min;
+        self.place();
+    }
+
+    /// Sets the maximum position and places the labels again.
+    pub fn set_max(&mut self, max: i32) {
+        self.max = max;
+        self.place();
+    }
+
+    /// Returns the permitted positions.
+    pub fn positions(&self) -> Vec<i32> {
+        let separation = self.clusters.separation;
+
+        self.clusters
+            .vec
+            .iter()
+            .flat_map(|cluster| cluster.positions(separation))
+            .collect()
+    }
+
+    /// Places the cached clusters with the current limits.
+    ///
+    /// Limits only move clusters towards each other, so the labels in each cluster placed without
+    /// limits remain in the same cluster with limits, and the clusters can be placed as units.
+    fn place(&mut self) {
+        let separation = self.clusters.separation;
+
+        self.clusters.vec.clear();
+
+        for cluster in &self.unlimited {
+            let mut cluster = cluster.limit(self.min, self.max);
+
+            while let Some(previous) = self.clusters.pop_if_not_separate(cluster) {
+                cluster = Cluster::merge(previous, cluster, separation).limit(self.min, self.max);
+            }
+
+            self.clusters.push(cluster);
+        }
+    }
+}
+
+/// Explains the placement of a label.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// The offset of the label from its preferred position.
+    pub moved_by: i32,
+    /// The index of the cluster containing the label.
+    pub cluster_index: usize,
+    /// The number of labels in the cluster containing the label.
+    pub cluster_member_count: usize,
+}
+
+/// Records a merge of two neighbouring clusters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MergeEvent {
+    /// The start and end positions of the first cluster before merging.
+    pub first: (i32, i32),
+    /// The start and end positions of the second cluster before merging.
+    pub second: (i32, i32),
+    /// The start and end positions of the merged cluster.
+    pub merged: (i32, i32),
+}
+
+/// Represents a connector from a data point to its label.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Connector {
+    /// The position of the data point, which is the preferred position of the label.
+    pub point: i32,
+    /// The permitted position of the label.
+    pub label_position: i32,
+    /// The offset of the label from the data point.
+    pub offset: i32,
+}
+
+/// Groups labels into clusters, respecting a minimum separation.
+fn cluster(positions: &[i32], separation: i32) -> ClusterList {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::merge(previous, cluster, separation);
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters
+}
+
+/// Places labels, respecting a minimum separation between each label and the previous label, and
+/// minimum and maximum positions for each label.
+///
+/// Subtracting the cumulative separation from each position reduces this to placing labels with a
+/// separation of zero, where each cluster is limited by the tightest limits of its labels.
+fn place_with_separations(
+    positions: &[i32],
+    separation: impl Fn(usize) -> i32,
+    limits: impl Fn(usize) -> (i32, i32),
+) -> Vec<i32> {
+    let mut offsets = Vec::with_capacity(positions.len());
+    let mut clusters = ClusterList::new(0, positions.len());
+    let mut cluster_limits = Vec::with_capacity(positions.len());
+
+    for (index, position) in positions.iter().enumerate() {
+        let offset = match offsets.last() {
+            Some(previous) => previous + separation(index),
+            None => 0,
+        };
+        offsets.push(offset);
+
+        let (min_position, max_position) = limits(index);
+        let mut min_limit = min_position.saturating_sub(offset);
+        let mut max_limit = max_position.saturating_sub(offset);
+
+        let mut cluster = Cluster::new(position - offset).limit(min_limit, max_limit);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            if let Some((previous_min, previous_max)) = cluster_limits.pop() {
+                min_limit = max(min_limit, previous_min);
+                max_limit = min(max_limit, previous_max);
+            }
+
+            cluster = Cluster::merge(previous, cluster, 0).limit(min_limit, max_limit);
+        }
+
+        clusters.push(cluster);
+        cluster_limits.push((min_limit, max_limit));
+    }
+
+    clusters
+        .positions()
+        .into_iter()
+        .zip(offsets)
+        .map(|(position, offset)| position + offset)
+        .collect()
+}
+
+/// Groups labels into clusters, respecting a minimum separation, returning `None` if the
+/// arithmetic overflows.
+#[cfg(feature = "checked")]
+fn checked_cluster(positions: &[i32], separation: i32) -> Option<ClusterList> {
+    let mut clusters = ClusterList::new(separation, positions.len());
+
+    for position in positions {
+        let mut cluster = Cluster::new(*position);
+
+        while let Some(previous) = clusters.pop_if_not_separate(cluster) {
+            cluster = Cluster::checked_merge(previous, cluster, separation)?;
+        }
+
+        clusters.push(cluster);
+    }
+
+    Some(clusters)
+}
+
+/// Groups labels with floating-point positions into clusters, respecting a minimum separation,
+/// returning the start position and number of labels of each cluster.
+fn cluster_f64(positions: &[f64], separation: f64) -> Vec<(f64, usize)> {
+    let mut clusters: Vec<(f64, f64, f64, usize)> = Vec::with_capacity(positions.len());
+
+    for position in positions {
+        let (mut start, mut min_offset, mut max_offset, mut len) = (*position, 0.0_f64, 0.0_f64, 1);
+
+        while let Some(&(previous_start, previous_min, previous_max, previous_len)) =
+            clusters.last()
+        {
+            let shift = start - separation * previous_len as f64 - previous_start;
+
+            if shift >= 0.0 {
+                break;
+            }
+
+            clusters.pop();
+
+            start = previous_start + shift;
+            min_offset = min_offset.min(previous_min + shift);
+            max_offset = max_offset.max(previous_max + shift);
+            len += previous_len;
+
+            let imbalance = (min_offset + max_offset) / 2.0;
+            start -= imbalance;
+            min_offset -= imbalance;
+            max_offset -= imbalance;
+        }
+
+        clusters.push((start, min_offset, max_offset, len));
+    }
+
+    clusters
+        .into_iter()
+        .map(|(start, _, _, len)| (start, len))
+        .collect()
+}
+
+/// Returns the start position for a cluster that minimises the maximum weighted absolute offset of
+/// its labels, choosing the start position closest to the current start position when several are
+/// equally good.
+fn weighted_start(positions: &[i32], weights: &[u32], separation: i32, current: i32) -> i32 {
+    let targets = cluster_targets(positions, separation);
+
+    let cost = |start: i64| {
+        targets
+            .iter()
+            .zip(weights)
+            .map(|(target, weight)| i64::from(*weight) * (start - target).abs())
+            .max()
+            .unwrap_or(0)
+    };
+
+    minimise_convex(&targets, current, cost)
+}
+
+/// Returns the start position for a cluster that minimises the maximum weighted absolute offset of
+/// its labels with fractional weights, choosing the start position closest to the current start
+/// position when several are equally good.
+fn weighted_start_f64(positions: &[i32], weights: &[f64], separation: i32, current: i32) -> i32 {
+    let targets = cluster_targets(positions, separation);
+
+    let cost = |start: i64| {
+        targets
+            .iter()
+            .zip(weights)
+            .map(|(target, weight)| weight * (start - target).abs() as f64)
+            .fold(0.0, f64::max)
+    };
+
+    minimise_convex(&targets, current, cost)
+}
+
+/// Returns the start position for a cluster that minimises the total cost of the offsets of its
+/// labels, choosing the start position closest to the current start position when several are
+/// equally good.
+fn cost_start(positions: &[i32], separation: i32, current: i32, cost: &impl Fn(i32) -> i64) -> i32 {
+    let targets = cluster_targets(positions, separation);
+
+    let total = |start: i64| {
+        targets
+            .iter()
+            .map(|target| cost((start - target) as i32))
+            .sum::<i64>()
+    };
+
+    minimise_convex(&targets, current, total)
+}
+
+/// Returns the start position for a cluster that minimises the maximum absolute offset of its
+/// labels and then the total absolute offset, choosing the start position closest to the current
+/// start position when several are equally good.
+fn lexicographic_start(positions: &[i32], separation: i32, current: i32) -> i32 {
+    let targets = cluster_targets(positions, separation);
+
+    let cost = |start: i64| {
+        targets.iter().fold((0, 0), |(max_offset, total), target| {
+            let offset = (start - target).abs();
+            (max(max_offset, offset), total + offset)
+        })
+    };
+
+    minimise_convex(&targets, current, cost)
+}
+
+/// Returns the start positions for a cluster at which each of its labels would be at its
+/// preferred position.
+fn cluster_targets(positions: &[i32], separation: i32) -> Vec<i64> {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(index, position)| i64::from(*position) - i64::from(separation) * index as i64)
+        .collect()
+}
+
+/// Returns the start position between the lowest and highest targets that minimises a convex
+/// function, choosing the start position closest to the current start position when several are
+/// equally good.
+fn minimise_convex<T: PartialOrd>(targets: &[i64], current: i32, f: impl Fn(i64) -> T) -> i32 {
+    let mut low = targets.iter().copied().min().unwrap_or(0);
+    let mut high = targets.iter().copied().max().unwrap_or(0);
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        if f(middle + 1) >= f(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    let first = low;
+    high = targets.iter().copied().max().unwrap_or(0);
+
+    while low < high {
+        let middle = low + (high - low + 1) / 2;
+
+        if f(middle - 1) >= f(middle) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+
+    i64::from(current).clamp(first, low) as i32
+}
+
+/// The constant mixed into seeds for `next_random()`, so that a seed of zero gives a non-zero
+/// state.
+const RANDOM_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// Advances a xorshift pseudo-random number generator, returning the new state.
+///
+/// The state must not be zero, as zero is a fixed point of the generator.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Represents a set of neighbouring labels whose permitted positions are separated by exactly the
+/// minimum separation.
+#[derive(Copy, Clone)]
+struct Cluster {
+    /// The start position.
+    start: i32,
+    /// The end position.
+    end: i32,
+    /// The minimum offset.
+    min_offset: i32,
+    /// The maximum offset.
+    max_offset: i32,
+    /// The offset of the first label.
+    first_offset: i32,
+    /// The number of labels.
+    len: usize,
+}
+
+impl Cluster {
+    /// Creates a new cluster containing a single position.
+    fn new(position: i32) -> Self {
+        Self {
+            start: position,
+            end: position,
+            min_offset: 0,
+            max_offset: 0,
+            first_offset: 0,
+            len: 1,
+        }
+    }
+
+    /// Creates a new cluster by merging two neighbouring clusters.
+    fn merge(first: Self, second: Self, separation: i32) -> Self {
+        Self::join(first, second, separation).balance()
+    }
+
+    /// Creates a new cluster by joining two neighbouring clusters, moving the first cluster to be
+    /// separated from the second by exactly the minimum separation, without balancing.
+    fn join(mut first: Self, second: Self, separation: i32) -> Self {
+        first.shift(second.start - first.end - separation);
+
+        Self {
+            start: first.start,
+            end: second.end,
+            min_offset: min(first.min_offset, second.min_offset),
+            max_offset: max(first.max_offset, second.max_offset),
+            first_offset: first.first_offset,
+            len: first.len + second.len,
+        }
+    }
+
+    /// Moves the cluster by an offset.
+    fn shift(&mut self, offset: i32) {
+        self.start += offset;
+        self.end += offset;
+        self.min_offset += offset;
+        self.max_offset += offset;
+        self.first_offset += offset;
+    }
+
+    /// Shifts the cluster to minimise the sum of `min_offset` and `max_offset`.
+    ///
+    /// This is equivalent to minimising the maximum absolute offset within the cluster. When the sum
+    /// is odd, two shifts are equally good; as integer division rounds towards zero, the smaller
+    /// of the two is chosen.
+    fn balance(mut self) -> Self {
+        let imbalance = (self.min_offset + self.max_offset) / 2;
+
+        if imbalance != 0 {
+            self.shift(-imbalance);
+        }
+
+        self
+    }
+
+    /// Shifts the cluster to minimise the sum of `min_offset` and `max_offset`, rounding the shift
+    /// as specified when the sum is odd.
+    fn balance_rounded(mut self, round: RoundMode) -> Self {
+        let imbalance = round.halve(self.min_offset + self.max_offset);
+
+        if imbalance != 0 {
+            self.shift(-imbalance);
+        }
+
+        self
+    }
+
+    /// Shifts the cluster to minimise the sum of `min_offset` and `max_offset`, choosing the shift
+    /// that minimises the absolute offset of the first label when two shifts are equally good.
+    fn balance_stable(mut self) -> Self {
+        let imbalance = (self.min_offset + self.max_offset).div_euclid(2);
+
+        if (self.min_offset + self.max_offset) % 2 != 0
+            && (self.first_offset - imbalance - 1).abs() < (self.first_offset - imbalance).abs()
+        {
+            self.shift(-imbalance - 1);
+        } else if imbalance != 0 {
+            self.shift(-imbalance);
+        }
+
+        self
+    }
+
+    /// Returns an iterator over the permitted positions of the labels in the cluster.
+    fn positions(self, separation: i32) -> impl Iterator<Item = i32> {
+        (0..self.len).map(move |index| self.start + separation * index as i32)
+    }
+
+    /// Shifts the cluster, if possible, so that no offset is greater than `max_up` or less than
+    /// `-max_down`.
+    fn bound_offsets(mut self, max_up: i32, max_down: i32) -> Option<Self> {
+        if i64::from(self.max_offset) - i64::from(self.min_offset)
+            > i64::from(max_up) + i64::from(max_down)
+        {
+            return None;
+        }
+
+        if self.max_offset > max_up {
+            self.shift(max_up - self.max_offset);
+        }
+
+        if self.min_offset < -max_down {
+            self.shift(-max_down - self.min_offset);
+        }
+
+        Some(self)
+    }
+
+    /// Shifts the cluster to respect the limits.
+    ///
+    /// The maximum absolute offset within the cluster is a convex function of its shift, minimised
+    /// by balancing, so shifting a balanced cluster only as far as necessary to respect the limits
+    /// minimises the maximum absolute offset subject to them. No further balancing is needed.
+    fn limit(mut self, min: i32, max: i32) -> Self {
+        if self.start < min {
+            self.shift(min - self.start);
+        }
+
+        if self.end > max {
+            self.shift(max - self.end);
+        }
+
+        self
+    }
+
+    /// Shifts the cluster to respect the limits, with the specified behaviour if the cluster does
+    /// not fit between them.
+    fn limit_with_overflow(mut self, min: i32, max: i32, overflow: Overflow) -> Self {
+        match overflow {
+            Overflow::Centre if self.end - self.start > max - min => {
+                self.shift(min + (max - min) / 2 - (self.start + (self.end - self.start) / 2));
+                self
+            }
+            _ => self.limit(min, max),
+        }
+    }
+}
+
+#[cfg(feature = "checked")]
+impl Cluster {
+    /// Creates a new cluster by merging two neighbouring clusters, returning `None` if the
+    /// arithmetic overflows.
+    fn checked_merge(mut first: Self, second: Self, separation: i32) -> Option<Self> {
+        first.checked_shift(
+            second
+                .start
+                .checked_sub(first.end)?
+                .checked_sub(separation)?,
+        )?;
+
+        Self {
+            start: first.start,
+            end: second.end,
+            min_offset: min(first.min_offset, second.min_offset),
+            max_offset: max(first.max_offset, second.max_offset),
+            first_offset: first.first_offset,
+            len: first.len + second.len,
+        }
+        .checked_balance()
+    }
+
+    /// Moves the cluster by an offset, returning `None` if the arithmetic overflows.
+    fn checked_shift(&mut self, offset: i32) -> Option<()> {
+        *self = Self {
+            start: self.start.checked_add(offset)?,
+            end: self.end.checked_add(offset)?,
+            min_offset: self.min_offset.checked_add(offset)?,
+            max_offset: self.max_offset.checked_add(offset)?,
+            first_offset: self.first_offset.checked_add(offset)?,
+            len: self.len,
+        };
+
+        Some(())
+    }
+
+    /// Shifts the cluster to minimise the sum of `min_offset` and `max_offset`, returning `None`
+    /// if the arithmetic overflows.
+    fn checked_balance(mut self) -> Option<Self> {
+        let imbalance = self.min_offset.checked_add(self.max_offset)? / 2;
+
+        if imbalance != 0 {
+            self.checked_shift(-imbalance)?;
+        }
+
+        Some(self)
+    }
+}
+
+/// Represents a list of clusters, providing stack-like access.
+struct ClusterList {
+    /// The vector of clusters.
+    vec: Vec<Cluster>,
+    /// The minimum separation.
+    separation: i32,
+    /// The requested capacity.
+    capacity: usize,
+}
+
+impl ClusterList {
+    /// Creates a new list of clusters.
+    ///
+    /// Providing a capacity equal to the number of labels prevents reallocation of vectors in
+    /// `push()` and `positions()`.
+    fn new(separation: i32, capacity: usize) -> Self {
+        Self {
+            vec: Vec::with_capacity(capacity),
+            separation,
+            capacity,
+        }
+    }
+
+    /// Pops and returns the last cluster from the list if it is not sufficiently separated from the
+    /// specified cluster, and otherwise returns `None`.
+    fn pop_if_not_separate(&mut self, cluster: Cluster) -> Option<Cluster> {
+        if let Some(previous) = self.vec.last() {
+            if i64::from(previous.end) + i64::from(self.separation) > i64::from(cluster.start) {
+                return self.vec.pop();
+            }
+        }
+
+        None
+    }
+
+    /// Pushes a cluster onto the end of the list.
+    fn push(&mut self, cluster: Cluster) {
+        self.vec.push(cluster);
+    }
+
+    /// Transforms the list into a vector of permitted positions.
+    fn positions(self) -> Vec<i32> {
+        let mut positions = Vec::with_capacity(self.capacity);
+        positions.extend(self.into_positions());
+        positions
+    }
+
+    /// Transforms the list into a vector of permitted positions in descending order.
+    fn reversed_positions(self) -> Vec<i32> {
+        let mut positions = Vec::with_capacity(self.capacity);
+
+        for cluster in self.vec.into_iter().rev() {
+            positions.extend(
+                (0..cluster.len)
+                    .rev()
+                    .map(|index| cluster.start + self.separation * index as i32),
+            );
+        }
+
+        positions
+    }
+
+    /// Transforms the list into a vector of permitted positions, returning `None` if the
+    /// arithmetic overflows.
+    #[cfg(feature = "checked")]
+    fn checked_positions(self) -> Option<Vec<i32>> {
+        let mut positions = Vec::with_capacity(self.capacity);
+
+        for cluster in self.vec {
+            for index in 0..cluster.len {
+                let offset = self.separation.checked_mul(i32::try_from(index).ok()?)?;
+                positions.push(cluster.start.checked_add(offset)?);
+            }
+        }
+
+        Some(positions)
+    }
+
+    /// Transforms the list into an iterator over the permitted positions.
+    fn into_positions(self) -> impl Iterator<Item = i32> {
+        let separation = self.separation;
+
+        self.vec
+            .into_iter()
+            .flat_map(move |cluster| cluster.positions(separation))
+    }
+
+    /// Transforms the list into a vector of the start position and number of labels of each
+    /// cluster.
+    fn runs(self) -> Vec<(i32, usize)> {
+        self.vec
+            .into_iter()
+            .map(|cluster| (cluster.start, cluster.len))
+            .collect()
+    }
+
+    /// Writes the permitted positions into a slice containing one element per label.
+    fn write_positions(self, positions: &mut [i32]) {
+        for (target, position) in positions.iter_mut().zip(self.into_positions()) {
+            *target = position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn already_separated() {
+        assert_eq!([0], *place(&[0], 10));
+        assert_eq!([0, 10], *place(&[0, 10], 10));
+        assert_eq!([-10, 0, 10], *place(&[-10, 0, 10], 10));
+    }
+
+    #[test]
+    fn already_separated_but_outside_limits() {
+        assert_eq!([-10], *place_with_limits(&[-20], 10, -10, 10));
+        assert_eq!([10], *place_with_limits(&[20], 10, -10, 10));
+        assert_eq!([-10, 10], *place_with_limits(&[-20, 20], 10, -10, 10));
+    }
+
+    #[test]
+    fn overflowing_limits() {
+        assert_eq!([-20, -10, 0], *place_with_limits(&[0, 0, 0], 10, 0, 0));
+    }
+
+    #[test]
+    fn one_cluster() {
+        assert_eq!([-5, 5], *place(&[0, 0], 10));
+        assert_eq!([-10, 0, 10], *place(&[0, 0, 0], 10));
+        assert_eq!([-20, -10, 0], *place_with_limits(&[0, 0, 0], 10, -20, 0));
+        assert_eq!([0, 10, 20], *place_with_limits(&[0, 0, 0], 10, 0, 20));
+    }
+
+    #[test]
+    fn two_clusters() {
+        assert_eq!(
+            [-30, -20, -10, 10, 20, 30],
             *place(&[-20, -20, -20, 20, 20, 20], 10)
         );
     }
 
     #[test]
-    fn cascading_merge() {
-        assert_eq!([-5, 5, 15, 25, 35], *place(&[0, 10, 20, 30, 31], 10));
+    fn cascading_merge() {
+        assert_eq!([-5, 5, 15, 25, 35], *place(&[0, 10, 20, 30, 31], 10));
+    }
+
+    #[test]
+    fn odd_separation() {
+        assert_eq!([-3, 2], *place(&[0, 0], 5));
+        assert_eq!([-5, 0, 5], *place(&[0, 0, 0], 5));
+        assert_eq!([-8, -3, 2, 7], *place(&[0, 0, 0, 0], 5));
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(Ok(()), validate(0, None));
+        assert_eq!(Ok(()), validate(10, Some((0, 0))));
+        assert_eq!(Err(PlacementError::NegativeSeparation), validate(-1, None));
+        assert_eq!(
+            Err(PlacementError::NegativeSeparation),
+            validate(-1, Some((100, 0)))
+        );
+        assert_eq!(
+            Err(PlacementError::InvalidLimits),
+            validate(10, Some((1, 0)))
+        );
+    }
+
+    #[test]
+    fn try_place_validates() {
+        assert_eq!(Ok(vec![-5, 5]), try_place(&[0, 0], 10));
+        assert_eq!(
+            Err(PlacementError::NegativeSeparation),
+            try_place(&[0, 0], -10)
+        );
+        assert_eq!(Ok(vec![0, 10]), try_place_with_limits(&[0, 0], 10, 0, 100));
+        assert_eq!(
+            Err(PlacementError::InvalidLimits),
+            try_place_with_limits(&[0, 0], 10, 100, 0)
+        );
+    }
+
+    #[test]
+    fn try_place_infeasible() {
+        assert_eq!(
+            Err(PlacementError::Infeasible),
+            try_place_with_limits(&vec![0; 10_000_000], 10, 0, 100)
+        );
+        assert_eq!(
+            Err(PlacementError::Infeasible),
+            try_place_with_limits(&[0, 0], i32::MAX, i32::MIN + 2, 0)
+        );
+        assert!(try_place_with_limits(&[0, 0], i32::MAX, i32::MIN + 1, 0).is_ok());
+        assert_eq!(Ok(vec![0, 10]), try_place_with_limits(&[0, 0], 10, 0, 10));
+        assert_eq!(Ok(vec![]), try_place_with_limits(&[], 10, 0, 0));
+    }
+
+    #[test]
+    fn within_extent() {
+        assert_eq!([-10, 0, 10], *place_within_extent(&[0, 0, 0], 10, 20));
+        assert_eq!([-10, 0, 10], *place_within_extent(&[0, 0, 0], 10, 100));
+        assert_eq!([-5, 0, 5], *place_within_extent(&[0, 0, 0], 10, 10));
+        assert_eq!([0, 0, 0], *place_within_extent(&[0, 0, 0], 10, 0));
+        assert_eq!([0, 10, 30], *place_within_extent(&[0, 10, 30], 10, 20));
+    }
+
+    #[test]
+    fn in_place() {
+        for (positions, separation) in [
+            (vec![0, 0, 0], 10),
+            (vec![-20, -20, -20, 20, 20, 20], 10),
+            (vec![0, 10, 20, 30, 31], 10),
+            (vec![0, 0, 0, 0], 5),
+        ] {
+            let mut placed = positions.clone();
+            place_in_place(&mut placed, separation);
+            assert_eq!(place(&positions, separation), placed);
+        }
+    }
+
+    #[test]
+    fn on_ticks() {
+        let ticks = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+        assert_eq!([40, 50, 60], *place_on_ticks(&[50, 50, 50], 10, &ticks));
+        assert_eq!([50, 60], *place_on_ticks(&[52, 52], 10, &ticks));
+        assert_eq!([40, 60], *place_on_ticks(&[52, 52], 15, &ticks));
+        assert_eq!(
+            [90, 100, 110],
+            *place_on_ticks(&[100, 100, 100], 10, &ticks)
+        );
+        assert_eq!([-5, 5], *place_on_ticks(&[0, 0], 10, &[]));
+    }
+
+    #[test]
+    fn rle() {
+        let positions = [-20, -20, -20, 20, 20, 20, 100];
+        let runs = place_rle(&positions, 10);
+        assert_eq!([(-30, 3), (10, 3), (100, 1)], *runs);
+
+        let expanded: Vec<i32> = runs
+            .iter()
+            .flat_map(|&(start, count)| (0..count).map(move |i| start + i as i32 * 10))
+            .collect();
+        assert_eq!(place(&positions, 10), expanded);
+    }
+
+    #[test]
+    fn already_separated_by_large_separation() {
+        assert_eq!([i32::MAX], *place(&[i32::MAX], i32::MAX));
+        assert_eq!([0, i32::MAX], *place(&[0, i32::MAX], i32::MAX));
+        assert_eq!(
+            [i32::MIN, 0, i32::MAX],
+            *place(&[i32::MIN, 0, i32::MAX], i32::MAX)
+        );
+        assert_eq!(
+            [-1_000_000, 1_000_000],
+            *place(&[-1_000_000, 1_000_000], 1_500_000)
+        );
+        assert_eq!([1, i32::MAX], *place(&[2, i32::MAX], i32::MAX - 1));
+    }
+
+    #[test]
+    fn diff() {
+        let previous = place(&[0, 20, 40], 10);
+        assert_eq!([None, None, None], *place_diff(&[0, 20, 40], 10, &previous));
+        assert_eq!(
+            [None, Some(25), None],
+            *place_diff(&[0, 25, 40], 10, &previous)
+        );
+        assert_eq!(
+            [None, None, None, Some(60)],
+            *place_diff(&[0, 20, 40, 60], 10, &previous)
+        );
+    }
+
+    #[test]
+    fn exclusive_limits() {
+        assert_eq!([0, 10], *place_with_limits(&[-5, 5], 10, 0, 20));
+        assert_eq!([1, 11], *place_with_exclusive_limits(&[-5, 5], 10, 0, 20));
+        assert_eq!([-10, 0], *place_with_limits(&[0, 10], 10, -20, 0));
+        assert_eq!(
+            [-11, -1],
+            *place_with_exclusive_limits(&[0, 10], 10, -20, 0)
+        );
+    }
+
+    #[test]
+    fn stable() {
+        assert_eq!([-3, 2], *place(&[0, 0], 5));
+        assert_eq!([-2, 3], *place_stable(&[0, 0], 5));
+        assert_eq!([-5, 0, 5], *place_stable(&[0, 0, 0], 5));
+        assert_eq!([-10, 0, 10], *place_stable(&[0, 0, 0], 10));
+        assert_eq!([-8, -3], *place(&[-5, -5], 5));
+        assert_eq!([-7, -2], *place_stable(&[-5, -5], 5));
+    }
+
+    #[test]
+    fn greedy() {
+        assert_eq!([0, 10, 20], *place_greedy(&[0, 0, 0], 10));
+        assert_eq!([-10, 0, 10], *place(&[0, 0, 0], 10));
+        assert_eq!([0, 10, 20, 30, 40], *place_greedy(&[0, 10, 20, 30, 31], 10));
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(
+            Stats {
+                merges: 4,
+                max_cluster_size: 5,
+                cluster_count: 1
+            },
+            place_with_stats(&[0, 10, 20, 30, 31], 10).1
+        );
+        assert_eq!(
+            Stats {
+                merges: 0,
+                max_cluster_size: 1,
+                cluster_count: 3
+            },
+            place_with_stats(&[0, 10, 20], 10).1
+        );
+        assert_eq!(Stats::default(), place_with_stats(&[], 10).1);
+    }
+
+    #[test]
+    fn sized_with_limits() {
+        let unlimited = |centers: &[i32], heights: &[i32], gap| {
+            place_sized_with_limits(centers, heights, gap, i32::MIN / 2, i32::MAX / 2)
+        };
+
+        assert_eq!([-10, 10], *unlimited(&[0, 0], &[20, 20], 0));
+        assert_eq!([-15, 15], *unlimited(&[0, 0], &[20, 40], 0));
+        assert_eq!([-13, 13], *unlimited(&[0, 0], &[20, 20], 6));
+        assert_eq!([0, 50], *unlimited(&[0, 50], &[20, 20], 6));
+        assert_eq!(
+            [0, 50, 85],
+            *place_sized_with_limits(&[0, 50, 95], &[10, 10, 30], 0, -100, 100)
+        );
+        assert_eq!(
+            [0, 40, 60],
+            *place_sized_with_limits(&[0, 50, 65], &[10, 10, 30], 0, -100, 75)
+        );
+        assert_eq!(
+            [-95, -85],
+            *place_sized_with_limits(&[-100, -100], &[10, 10], 0, -100, 100)
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(
+            [-25, -15, -5, 5],
+            *place_with_overflow(&[0, 0, 0, 0], 10, -5, 5, Overflow::Max)
+        );
+        assert_eq!(
+            [-15, -5, 5, 15],
+            *place_with_overflow(&[0, 0, 0, 0], 10, -5, 5, Overflow::Centre)
+        );
+        assert_eq!(
+            [0, 10, 20],
+            *place_with_overflow(&[0, 0, 0], 10, 0, 100, Overflow::Centre)
+        );
+    }
+
+    #[test]
+    fn container() {
+        let placement = Placement::for_container(100, 10).unwrap();
+        assert_eq!((10, 90), (placement.min(), placement.max()));
+        assert_eq!([10, 20], *placement.place(&[0, 0], 10));
+        assert_eq!([80, 90], *placement.place(&[100, 100], 10));
+        assert_eq!(
+            Ok(Placement::new(50, 50).unwrap()),
+            Placement::for_container(100, 50)
+        );
+        assert_eq!(
+            Err(PlacementError::InvalidLimits),
+            Placement::for_container(100, 51)
+        );
+        assert_eq!(
+            Err(PlacementError::InvalidLimits),
+            Placement::for_container(i32::MIN, 1)
+        );
+    }
+
+    #[test]
+    fn collect() {
+        use std::collections::{BTreeSet, VecDeque};
+
+        let positions = [-20, -20, -20, 20, 20, 20];
+        let placed = place(&positions, 10);
+
+        let deque: VecDeque<i32> = place_collect(&positions, 10);
+        assert_eq!(placed, Vec::from(deque));
+
+        let set: BTreeSet<i32> = place_collect(&positions, 10);
+        assert_eq!(placed, set.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn worst() {
+        assert_eq!(None, worst_label(&[], 10));
+        assert_eq!(Some((0, 0)), worst_label(&[0, 10, 20], 10));
+        assert_eq!(Some((0, -10)), worst_label(&[0, 0, 0, 30], 10));
+        assert_eq!(Some((1, -9)), worst_label(&[0, 20, 21, 22], 10));
+    }
+
+    #[test]
+    fn f64_rounded() {
+        let naive: Vec<i32> = [7.5_f64, 12.5]
+            .iter()
+            .map(|position| position.round_ties_even() as i32)
+            .collect();
+        assert_eq!([8, 12], *naive);
+        assert_eq!(
+            [8, 13],
+            *place_f64_rounded(&[7.5, 12.5], 5.0, Rounding::HalfEven)
+        );
+        assert_eq!(
+            [7, 12],
+            *place_f64_rounded(&[7.5, 12.5], 5.0, Rounding::Floor)
+        );
+        assert_eq!(
+            [8, 13],
+            *place_f64_rounded(&[7.5, 12.5], 5.0, Rounding::Ceil)
+        );
+        assert_eq!(
+            [-3, 2],
+            *place_f64_rounded(&[0.3, 0.3], 5.0, Rounding::Floor)
+        );
+        assert_eq!(
+            [-2, 3],
+            *place_f64_rounded(&[0.3, 0.3], 5.0, Rounding::Ceil)
+        );
+
+        assert_eq!(
+            [-5, 5],
+            *place_f64_rounded(&[0.0, 0.0], 10.0, Rounding::Floor)
+        );
+        assert_eq!(
+            [-3, 2],
+            *place_f64_rounded(&[0.0, 0.0], 5.0, Rounding::Floor)
+        );
+        assert_eq!(
+            [-2, 3],
+            *place_f64_rounded(&[0.0, 0.0], 5.0, Rounding::Ceil)
+        );
+        assert_eq!(
+            [-2, 3],
+            *place_f64_rounded(&[0.0, 0.0], 5.0, Rounding::HalfUp)
+        );
+        assert_eq!(
+            [-2, 3],
+            *place_f64_rounded(&[0.0, 0.0], 5.0, Rounding::HalfEven)
+        );
+        assert_eq!(
+            [0, 10, 21],
+            *place_f64_rounded(&[0.4, 10.4, 20.6], 9.5, Rounding::HalfUp)
+        );
+
+        for (positions, separation) in random_inputs() {
+            let positions: Vec<f64> = positions
+                .iter()
+                .map(|position| f64::from(*position) + 0.5)
+                .collect();
+            let separation = f64::from(separation) - 0.5;
+
+            for rounding in [
+                Rounding::Floor,
+                Rounding::Ceil,
+                Rounding::HalfUp,
+                Rounding::HalfEven,
+            ] {
+                let placed = place_f64_rounded(&positions, separation, rounding);
+                assert!(is_valid_placement(
+                    &placed,
+                    separation.ceil() as i32,
+                    None,
+                    None
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn required() {
+        let place = |required: &[bool]| place_with_required(&[0, 0, 0], 10, 0, 10, required);
+
+        assert_eq!([-10, 0, 10], *place(&[false, false, false]));
+        assert_eq!([0, 10, 20], *place(&[true, false, false]));
+        assert_eq!([-10, 0, 10], *place(&[false, true, true]));
+        assert_eq!([-10, 0, 10], *place(&[true, true, true]));
+        assert_eq!(
+            [0, 10],
+            *place_with_required(&[0, 0], 10, 0, 100, &[false, true])
+        );
+        assert_eq!(
+            [0, 10, 20, 30],
+            *place_with_required(&[0, 0, 0, 30], 10, 0, 10, &[true, false, false, false])
+        );
+    }
+
+    #[test]
+    fn fold() {
+        let positions = [-20, -20, -20, 20, 20, 20, 100];
+        assert_eq!(
+            place(&positions, 10).iter().sum::<i32>(),
+            place_fold(&positions, 10, 0, |sum, _, position| sum + position)
+        );
+        assert_eq!(
+            21,
+            place_fold(&positions, 10, 0, |sum, index, _| sum + index)
+        );
+    }
+
+    /// Returns sorted pseudo-random positions, using a fixed seed so failures are reproducible.
+    fn random_inputs() -> Vec<(Vec<i32>, i32)> {
+        let mut state = RANDOM_SEED;
+        let mut next = move |bound: u64| next_random(&mut state) % bound;
+
+        let mut inputs = vec![
+            (vec![], 10),
+            (vec![0], 10),
+            (vec![0, 0], 10),
+            (vec![0, 0, 0], 10),
+            (vec![-20, -20, -20, 20, 20, 20], 10),
+            (vec![0, 10, 20, 30, 31], 10),
+            (vec![0, 0], 5),
+            (vec![0, 0, 0], 5),
+            (vec![0, 0, 0, 0], 5),
+        ];
+
+        for _ in 0..500 {
+            let separation = next(20) as i32;
+            let mut positions: Vec<i32> = (0..next(30)).map(|_| next(400) as i32 - 200).collect();
+            positions.sort_unstable();
+            inputs.push((positions, separation));
+        }
+
+        inputs
+    }
+
+    #[test]
+    fn paths_agree() {
+        for (positions, separation) in random_inputs() {
+            let placed = place(&positions, separation);
+
+            assert_eq!(
+                placed,
+                place_with_limits(&positions, separation, i32::MIN / 2, i32::MAX / 2)
+            );
+            assert_eq!(
+                placed,
+                place_with_separations(&positions, |_| separation, |_| (i32::MIN, i32::MAX))
+            );
+            assert_eq!(placed, cluster(&positions, separation).positions());
+
+            let mut in_place = positions.clone();
+            place_in_place(&mut in_place, separation);
+            assert_eq!(placed, in_place);
+        }
+    }
+
+    #[test]
+    fn scaled_separation() {
+        let positions = [0, 0, 0, 25];
+        let mut previous_extent = 0;
+
+        for (scale_num, scale_den) in [(1, 4), (1, 2), (2, 3), (1, 1), (3, 2), (2, 1)] {
+            let placed = place_scaled_separation(&positions, 10, scale_num, scale_den);
+            assert_eq!(place(&positions, 10 * scale_num / scale_den), placed);
+
+            let extent = placed[3] - placed[0];
+            assert!(extent >= previous_extent);
+            previous_extent = extent;
+        }
+
+        assert_eq!(
+            [-50_000, 50_000],
+            *place_scaled_separation(&[0, 0], 100_000, 100_000, 100_000)
+        );
+    }
+
+    #[test]
+    fn single() {
+        for (positions, separation) in random_inputs() {
+            let placed = place(&positions, separation);
+
+            for (index, position) in placed.iter().enumerate() {
+                assert_eq!(*position, place_single(&positions, separation, index));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn single_out_of_bounds() {
+        place_single(&[0, 0], 10, 2);
+    }
+
+    #[test]
+    fn signature() {
+        assert_eq!(
+            place_signature(&[0, 0, 0], 10),
+            place_signature(&[-10, 0, 10], 10)
+        );
+        assert_ne!(
+            place_signature(&[0, 10, 20], 10),
+            place_signature(&[0, 10, 21], 10)
+        );
+        assert_ne!(
+            place_signature(&[0, 10, 20], 10),
+            place_signature(&[0, 10, 20], 5)
+        );
+        assert_ne!(place_signature(&[], 10), place_signature(&[0], 10));
+    }
+
+    #[test]
+    fn columns() {
+        let (left, right) = place_columns(&[0, 10, 20, 30], &[5, 15, 25, 35], 10);
+        assert_eq!([0, 10, 20, 30], *left);
+        assert_eq!([5, 15, 25, 35], *right);
+
+        let (left, right) = place_columns(&[0, 0, 20], &[10, 10], 10);
+        assert_eq!([-5, 5, 20], *left);
+        assert_eq!([5, 15], *right);
+    }
+
+    #[test]
+    fn approximate() {
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_approximate(&positions, separation, positions.len())
+            );
+        }
+
+        assert_eq!([0, 10, 20], *place_approximate(&[0, 0, 0], 10, 0));
+        assert_eq!([-10, 0, 10], *place_approximate(&[0, 0, 0], 10, 1));
+        assert_eq!(
+            [0, 10, 20, 30, 40],
+            *place_approximate(&[0, 10, 20, 30, 31], 10, 1)
+        );
+        assert_eq!(
+            [0, 10, 20, 30, 40],
+            *place_approximate(&[0, 10, 20, 30, 31], 10, 2)
+        );
+        assert_eq!(
+            [-5, 5, 15, 25, 35],
+            *place_approximate(&[0, 10, 20, 30, 31], 10, 4)
+        );
+    }
+
+    #[test]
+    fn limits_checked() {
+        assert_eq!(
+            (vec![-25, -15, -5, 5], false),
+            place_with_limits_checked(&[0, 0, 0, 0], 10, -5, 5, Overflow::Max)
+        );
+        assert_eq!(
+            (vec![-15, -5, 5, 15], false),
+            place_with_limits_checked(&[0, 0, 0, 0], 10, -5, 5, Overflow::Centre)
+        );
+        assert_eq!(
+            (vec![-5, -5, -5, 5], true),
+            place_with_limits_checked(&[0, 0, 0, 0], 10, -5, 5, Overflow::Clamp)
+        );
+        assert_eq!(
+            (vec![-5, 5], false),
+            place_with_limits_checked(&[0, 0], 10, -5, 5, Overflow::Clamp)
+        );
+    }
+
+    #[test]
+    fn ordered() {
+        assert_eq!([-10, 0, 10], *place_ordered(&[0, 0, 0], 10, &[0, 1, 2]));
+        assert_eq!([10, -10, 0], *place_ordered(&[0, 0, 0], 10, &[2, 0, 1]));
+        assert_eq!([-10, 0, 10], *place_ordered(&[0, 0, 0], 10, &[1, 1, 1]));
+        assert_eq!(
+            [-15, 15, 5, -5],
+            *place_ordered(&[-10, 10, 0, 0], 10, &[0, 0, 1, 0])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn ordered_too_many_ranks() {
+        place_ordered(&[0, 0], 10, &[0, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn checked_overflow() {
+        assert_eq!(
+            Err(PlacementError::Overflow),
+            try_place(&[i32::MAX, i32::MAX], 10)
+        );
+        assert_eq!(
+            Err(PlacementError::Overflow),
+            try_place(&[i32::MIN, i32::MIN], 10)
+        );
+        assert_eq!(
+            Ok(vec![i32::MAX - 15, i32::MAX - 5]),
+            try_place(&[i32::MAX - 10, i32::MAX - 10], 10)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                Ok(place(&positions, separation)),
+                try_place(&positions, separation)
+            );
+        }
+    }
+
+    #[test]
+    fn full() {
+        let unlimited = |positions: &[i32], weights: &[u32]| {
+            place_full(positions, 10, weights, i32::MIN / 2, i32::MAX / 2)
+        };
+
+        assert_eq!([-2, 8], *unlimited(&[0, 0], &[3, 1]));
+        assert_eq!([-8, 2], *unlimited(&[0, 0], &[1, 3]));
+        assert_eq!([-10, 0], *unlimited(&[0, 0], &[0, 1]));
+        assert_eq!([-8, 2, 12], *unlimited(&[0, 0, 10], &[1, 3, 1]));
+
+        for (positions, separation) in random_inputs() {
+            for weight in [0, 2] {
+                let weights = vec![weight; positions.len()];
+                assert_eq!(
+                    place_with_limits(&positions, separation, -50, 50),
+                    place_full(&positions, separation, &weights, -50, 50)
+                );
+            }
+        }
+
+        assert_eq!([0, 10], *place_full(&[0, 0], 10, &[3, 1], 0, 100));
+        assert_eq!([-10, 0], *place_full(&[0, 0], 10, &[3, 1], -100, 0));
+        assert_eq!(
+            [-20, -10, 0],
+            *place_full(&[0, 0, 0], 10, &[5, 1, 1], -100, 0)
+        );
+    }
+
+    #[test]
+    fn positions_and_offsets() {
+        for (positions, separation) in random_inputs() {
+            let (placed, offsets) = place_positions_and_offsets(&positions, separation);
+            assert_eq!(place(&positions, separation), placed);
+            assert_eq!(positions.len(), offsets.len());
+
+            for index in 0..positions.len() {
+                assert_eq!(positions[index], placed[index] - offsets[index]);
+            }
+        }
+    }
+
+    #[test]
+    fn by_comparator() {
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_by(&positions, separation, |a, b| a.cmp(&b))
+            );
+
+            let descending: Vec<i32> = positions.iter().map(|p| -p).collect();
+            let expected: Vec<i32> = place(&positions, separation).iter().map(|p| -p).collect();
+            assert_eq!(
+                expected,
+                place_by(&descending, separation, |a, b| b.cmp(&a))
+            );
+        }
+
+        assert_eq!(
+            [i32::MIN + 10, i32::MIN],
+            *place_by(&[i32::MIN + 10, i32::MIN], 10, |a, b| b.cmp(&a))
+        );
+    }
+
+    #[test]
+    fn balance_ranges() {
+        assert_eq!(
+            [-5..=-5, 95..=105],
+            *cluster_balance_ranges(&[0, 0, 100], 10)
+        );
+        assert_eq!([-5..=-5, 15..=23], *cluster_balance_ranges(&[0, 0, 18], 10));
+        assert_eq!(
+            [-10..=-10, 20..=30, 35..=50],
+            *cluster_balance_ranges(&[0, 0, 0, 25, 40], 10)
+        );
+        assert_eq!([-3..=-2], *cluster_balance_ranges(&[0, 0], 5));
+        assert!(cluster_balance_ranges(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn incremental_remove() {
+        for (positions, separation) in random_inputs() {
+            let mut placer = IncrementalPlacer::new(separation);
+            for position in &positions {
+                placer.insert(*position);
+            }
+            assert_eq!(place(&positions, separation), placer.positions());
+
+            for (index, position) in [15, -3, 0, 150, -150].into_iter().enumerate() {
+                let inserted = placer.insert(position);
+                assert_eq!(position, placer.remove(inserted));
+                assert_eq!(place(&positions, separation), placer.positions());
+
+                if index < positions.len() {
+                    let mut remaining = positions.clone();
+                    remaining.remove(index);
+
+                    let removed = placer.remove(index);
+                    assert_eq!(place(&remaining, separation), placer.positions());
+                    placer.insert(removed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_insert_first() {
+        for (positions, separation) in random_inputs() {
+            let mut placer = IncrementalPlacer::new(separation);
+
+            for (index, position) in positions.iter().enumerate().rev() {
+                placer.insert(*position);
+                assert_eq!(place(&positions[index..], separation), placer.positions());
+            }
+        }
+    }
+
+    #[test]
+    fn cow() {
+        assert!(matches!(place_cow(&[], 10), Cow::Borrowed(&[])));
+        assert!(matches!(
+            place_cow(&[0, 10, 20], 10),
+            Cow::Borrowed(&[0, 10, 20])
+        ));
+
+        let placed = place_cow(&[0, 0], 10);
+        assert!(matches!(placed, Cow::Owned(_)));
+        assert_eq!([-5, 5], *placed);
+    }
+
+    #[test]
+    fn spread() {
+        assert_eq!([0, 12], *place_spread(&[0, 12], 10, 10, -100, 100));
+        assert_eq!([-5, 15], *place_spread(&[0, 11], 10, 20, -100, 100));
+        assert_eq!([0, 15], *place_spread(&[0, 11], 10, 20, 0, 15));
+        assert_eq!([0, 30], *place_spread(&[0, 30], 10, 20, -100, 100));
+        assert_eq!([-5, 5], *place_spread(&[0, 0], 10, 20, 0, 5));
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            "the minimum separation is negative",
+            PlacementError::NegativeSeparation.to_string()
+        );
+        assert_eq!(
+            "the minimum position is greater than the maximum position",
+            PlacementError::InvalidLimits.to_string()
+        );
+        assert_eq!(
+            "the permitted positions overflow the range of i32",
+            PlacementError::Overflow.to_string()
+        );
+        assert_eq!(
+            "the limits do not provide sufficient space for the labels",
+            PlacementError::Infeasible.to_string()
+        );
+        assert_eq!(
+            "a label exceeds the maximum permitted offset",
+            PlacementError::OffsetCapExceeded.to_string()
+        );
+        assert_eq!(
+            "the placement exceeds the permitted number of operations",
+            PlacementError::Budget.to_string()
+        );
+
+        let error: Box<dyn Error> = Box::new(PlacementError::InvalidLimits);
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn cost() {
+        let squared = |offset: i32| i64::from(offset) * i64::from(offset);
+        let absolute = |offset: i32| i64::from(offset.abs());
+
+        assert_eq!([-10, 0, 10, 20], *place(&[0, 0, 0, 15], 10));
+        assert_eq!([-10, 0, 10], *place_with_cost(&[0, 0, 0], 10, squared));
+        assert_eq!(
+            [-11, -1, 9, 19],
+            *place_with_cost(&[0, 0, 0, 15], 10, squared)
+        );
+        assert_eq!(
+            [-15, -5, 5, 15],
+            *place_with_cost(&[0, 0, 0, 15], 10, absolute)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert!(are_separated(
+                &place_with_cost(&positions, separation, squared),
+                separation
+            ));
+        }
+    }
+
+    #[test]
+    fn in_slots() {
+        assert_eq!(vec![1, 2], place_in_slots(&[9, 11], 4, 0, 10));
+        assert_eq!(vec![2, 3], place_in_slots(&[29, 31], 4, 0, 10));
+        assert_eq!(vec![0, 1, 3], place_in_slots(&[-100, -50, 100], 4, 0, 10));
+        assert_eq!(Vec::<usize>::new(), place_in_slots(&[], 0, 0, 10));
+    }
+
+    #[test]
+    fn avoiding_zone() {
+        assert_eq!(
+            vec![-6, 4, 25, 35],
+            place_avoiding_zone(&[0, 10, 20, 30], 10, 5, 25)
+        );
+
+        assert_eq!(
+            vec![0, 10, 20, 30],
+            place_avoiding_zone(&[0, 10, 20, 30], 10, 12, 18)
+        );
+
+        assert_eq!(
+            vec![-20, -10, 0],
+            place_avoiding_zone(&[0, 0, 0], 10, 1, 22)
+        );
+        assert_eq!(
+            vec![0, 10, 20],
+            place_avoiding_zone(&[10, 10, 10], 10, 5, 5)
+        );
+    }
+
+    #[test]
+    fn slice_into() {
+        let positions = [99, 99, 0, 0, 0, 99, 99];
+        let mut out = [1; 7];
+
+        place_slice_into(&positions[2..5], 10, &mut out[2..5]);
+
+        assert_eq!([1, 1, -10, 0, 10, 1, 1], out);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_into_mismatched_length() {
+        place_slice_into(&[0, 0], 10, &mut [0; 3]);
+    }
+
+    #[test]
+    fn grouped() {
+        assert_eq!(
+            vec![10, 20, 100],
+            place_grouped(&[0, 30, 100], 10, &[1, 1, 2])
+        );
+        assert_eq!(
+            vec![0, 30, 100],
+            place_grouped(&[0, 30, 100], 10, &[1, 2, 3])
+        );
+        assert_eq!(
+            vec![35, 45, 55],
+            place_grouped(&[0, 45, 90], 10, &[1, 1, 1])
+        );
+
+        for (positions, separation) in random_inputs() {
+            let group_ids: Vec<u32> = (0..positions.len() as u32).collect();
+
+            assert_eq!(
+                place(&positions, separation),
+                place_grouped(&positions, separation, &group_ids)
+            );
+        }
+    }
+
+    #[test]
+    fn valid_placement() {
+        assert!(is_valid_placement(&[], 10, Some(0), Some(0)));
+        assert!(is_valid_placement(&[0, 10, 20], 10, Some(0), Some(20)));
+        assert!(is_valid_placement(&[0, 0], 0, None, None));
+        assert!(!is_valid_placement(&[10, 0], 0, None, None));
+        assert!(!is_valid_placement(&[0, 9], 10, None, None));
+        assert!(!is_valid_placement(&[0, 10], 10, Some(1), None));
+        assert!(!is_valid_placement(&[0, 10], 10, None, Some(9)));
+
+        for (positions, separation) in random_inputs() {
+            assert!(is_valid_placement(
+                &place(&positions, separation),
+                separation,
+                None,
+                None
+            ));
+        }
+    }
+
+    #[test]
+    fn offset_bounds() {
+        assert_eq!(
+            Ok(vec![-8, 2]),
+            place_with_offset_bounds(&[0, 0], 10, 2, 20)
+        );
+        assert_eq!(
+            Ok(vec![-2, 8]),
+            place_with_offset_bounds(&[0, 0], 10, 20, 2)
+        );
+        assert_eq!(Ok(vec![-5, 5]), place_with_offset_bounds(&[0, 0], 10, 5, 5));
+
+        assert_eq!(
+            Ok(vec![-33, -23, -13, -3]),
+            place_with_offset_bounds(&[-20, -20, -5, -5], 10, 2, 20)
+        );
+
+        assert_eq!(
+            Err(PlacementError::OffsetCapExceeded),
+            place_with_offset_bounds(&[0, 0], 10, 2, 2)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bench")]
+    fn generated_positions() {
+        for seed in 0..20 {
+            for clusters in [0, 1, 5, 100] {
+                let positions = generate_positions(200, clusters, seed);
+
+                assert_eq!(200, positions.len());
+                assert!(positions.windows(2).all(|pair| pair[0] <= pair[1]));
+                assert_eq!(positions, generate_positions(200, clusters, seed));
+            }
+        }
+
+        assert_ne!(generate_positions(200, 5, 1), generate_positions(200, 5, 2));
+        assert!(generate_positions(0, 5, 1).is_empty());
+    }
+
+    #[test]
+    fn sized_intervals() {
+        let centers = [0, 3, 5, 40, 41];
+        let heights = [10, 7, 20, 5, 12];
+
+        let intervals = place_sized_intervals(&centers, &heights, 3);
+
+        for ((start, end), height) in intervals.iter().zip(heights) {
+            assert_eq!(height, end - start);
+        }
+
+        for pair in intervals.windows(2) {
+            assert!(pair[0].1 + 3 <= pair[1].0);
+        }
+
+        assert_eq!(vec![(-5, 5)], place_sized_intervals(&[0], &[10], 3));
+    }
+
+    #[test]
+    fn bounded() {
+        assert_eq!(Ok(vec![]), try_place_bounded(&[], 10, 0));
+        assert_eq!(Ok(vec![0, 10, 20]), try_place_bounded(&[0, 10, 20], 10, 0));
+        assert_eq!(
+            Err(PlacementError::Budget),
+            try_place_bounded(&[0, 0, 0], 10, 1)
+        );
+
+        assert_eq!(
+            Err(PlacementError::NegativeSeparation),
+            try_place_bounded(&[0, 0], -10, 100)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                Ok(place(&positions, separation)),
+                try_place_bounded(&positions, separation, positions.len())
+            );
+        }
+    }
+
+    #[test]
+    fn offsets_f32() {
+        for (positions, separation) in random_inputs() {
+            let (_, offsets) = place_positions_and_offsets(&positions, separation);
+
+            assert_eq!(
+                offsets
+                    .iter()
+                    .map(|offset| *offset as f32)
+                    .collect::<Vec<_>>(),
+                place_offsets_f32(&positions, separation)
+            );
+        }
+    }
+
+    #[test]
+    fn viewport() {
+        let positions = [-1000, -995, 0, 1, 2, 1000, 1003];
+        let permitted_positions = place_with_viewport(&positions, 10, 0..10);
+
+        assert_eq!(place(&[0, 1, 2], 10), permitted_positions[2..5]);
+        assert_eq!([-1000, -995], permitted_positions[..2]);
+        assert_eq!([1000, 1003], permitted_positions[5..]);
+
+        assert_eq!(
+            place(&[-12, 0, 0], 10),
+            place_with_viewport(&[-12, 0, 0], 10, 0..10)
+        );
+
+        assert_eq!(vec![0, 100], place_with_viewport(&[0, 100], 10, 20..30));
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_with_viewport(&positions, separation, -200..200)
+            );
+        }
+    }
+
+    #[test]
+    fn compress() {
+        assert_eq!(
+            [0, 5, 10, 15],
+            *place_with_overflow(&[0, 0, 0, 0], 10, 0, 15, Overflow::Compress)
+        );
+        assert_eq!(
+            [-5, -2, 1, 5],
+            *place_with_overflow(&[0, 0, 0, 0], 10, -5, 5, Overflow::Compress)
+        );
+        assert_eq!(
+            [0, 10, 20],
+            *place_with_overflow(&[0, 0, 0], 10, 0, 100, Overflow::Compress)
+        );
+        assert_eq!(
+            [0, 10, 20],
+            *place_with_overflow(&[0, 0, 0], 10, 0, 20, Overflow::Compress)
+        );
+    }
+
+    #[test]
+    fn merged_placements() {
+        assert_eq!(
+            vec![-3, 7, 17, 27],
+            merge_placements(&[0, 10], &[15, 25], 10)
+        );
+        assert_eq!(
+            vec![0, 10, 30, 40],
+            merge_placements(&[0, 10], &[30, 40], 10)
+        );
+        assert_eq!(vec![0, 10], merge_placements(&[], &[0, 10], 10));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(vec![-12, 0, 12], place_display(&[0, 0, 0], 10, 12));
+        assert_eq!(vec![-10, 10, 25], place_display(&[0, 0, 25], 10, 20));
+        assert_eq!(vec![-10, 10, 20], place_display(&[0, 0, 17], 10, 20));
+
+        let permitted_positions = place_display(&[0, 1, 2, 3, 50], 10, 15);
+        let gaps: Vec<i32> = permitted_positions
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect();
+
+        assert_eq!([15, 15, 15], gaps[..3]);
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_display(&positions, separation, separation)
+            );
+        }
+    }
+
+    #[test]
+    fn circular() {
+        let separated = |placed: &[i32]| {
+            let mut sorted = placed.to_vec();
+            sorted.sort_unstable();
+
+            sorted.windows(2).all(|pair| pair[1] - pair[0] >= 10)
+                && sorted[0] + 360 - sorted[sorted.len() - 1] >= 10
+        };
+
+        let placed = place_circular(&[0, 1, 358, 359], 10, 360);
+        assert!(separated(&placed));
+
+        let placed = place_circular(&[0, 0, 1, 2, 355, 359], 10, 360);
+        assert!(separated(&placed));
+
+        let crowded: Vec<i32> = (0..36).map(|index| index * 3).collect();
+        assert!(separated(&place_circular(&crowded, 10, 360)));
+
+        assert_eq!(vec![0, 100, 200], place_circular(&[0, 100, 200], 10, 360));
+        assert_eq!(vec![7], place_circular(&[7], 10, 360));
+
+        for overfull in [vec![0, 0, 0, 0], vec![0, 5, 10, 15, 20]] {
+            let placed = place_circular(&overfull, 10, 30);
+            assert!(placed.iter().all(|position| (0..30).contains(position)));
+            assert!(placed
+                .windows(2)
+                .all(|pair| (pair[1] - pair[0]).rem_euclid(30) == 10));
+        }
+    }
+
+    #[test]
+    fn separated() {
+        assert!(are_separated(&[], 10));
+        assert!(are_separated(&[0], 10));
+        assert!(are_separated(&[0, 10, 30], 10));
+        assert!(are_separated(&[0, 0], 0));
+        assert!(!are_separated(&[0, 0], 10));
+        assert!(!are_separated(&[0, 10, 19], 10));
+        assert!(are_separated(&[i32::MIN, i32::MAX], i32::MAX));
+    }
+
+    #[test]
+    fn round_mode() {
+        assert_eq!(
+            vec![-6, 5],
+            place_with_round(&[0, 0], 11, RoundMode::TowardZero)
+        );
+        assert_eq!(
+            vec![-5, 6],
+            place_with_round(&[0, 0], 11, RoundMode::TowardNegInf)
+        );
+        assert_eq!(
+            vec![-5, 6],
+            place_with_round(&[0, 0], 11, RoundMode::Nearest)
+        );
+        assert_eq!(
+            vec![-3, 4],
+            place_with_round(&[0, 0], 7, RoundMode::Nearest)
+        );
+        assert_eq!(
+            vec![-4, 3],
+            place_with_round(&[0, 0], 7, RoundMode::TowardZero)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_with_round(&positions, separation, RoundMode::TowardZero)
+            );
+        }
+    }
+
+    #[test]
+    fn with_and_without_limits() {
+        for (positions, separation) in random_inputs() {
+            for (min, max) in [(-50, 50), (-500, 500), (i32::MIN, i32::MAX)] {
+                assert_eq!(
+                    (
+                        place_with_limits(&positions, separation, min, max),
+                        place(&positions, separation)
+                    ),
+                    place_with_and_without_limits(&positions, separation, min, max)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn member_offsets() {
+        assert_eq!(
+            vec![vec![-10, 0, 10]],
+            cluster_member_offsets(&[0, 0, 0], 10)
+        );
+        assert_eq!(Vec::<Vec<i32>>::new(), cluster_member_offsets(&[], 10));
+
+        for (positions, separation) in random_inputs() {
+            let (_, offsets) = place_positions_and_offsets(&positions, separation);
+
+            assert_eq!(
+                offsets,
+                cluster_member_offsets(&positions, separation).concat()
+            );
+        }
+    }
+
+    #[test]
+    fn anchor_first() {
+        assert_eq!(vec![0, 10, 30, 40], place_anchor_first(&[0, 0, 30, 31], 10));
+        assert_eq!(vec![0, 10, 20], place_anchor_first(&[0, 0, 0], 10));
+        assert_eq!(vec![0, 10, 20, 30], place_anchor_first(&[0, 5, 20, 21], 10));
+        assert_eq!(Vec::<i32>::new(), place_anchor_first(&[], 10));
+
+        for (positions, separation) in random_inputs() {
+            let placed = place_anchor_first(&positions, separation);
+
+            assert!(is_valid_placement(&placed, separation, None, None));
+            assert_eq!(place_greedy(&positions, separation), placed);
+
+            for (placed, preferred) in placed.iter().zip(&positions) {
+                assert!(placed >= preferred);
+            }
+        }
+    }
+
+    #[test]
+    fn max_offset_is_monotonic() {
+        for (positions, _) in random_inputs() {
+            let max_offsets: Vec<i32> = (0..40)
+                .map(|separation| max_offset_only(&positions, separation))
+                .collect();
+
+            assert!(max_offsets.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            assert_eq!(
+                worst_label(&positions, 10).map_or(0, |(_, offset)| offset.abs()),
+                max_offset_only(&positions, 10)
+            );
+        }
+    }
+
+    #[test]
+    fn max_offset_large_separation() {
+        assert_eq!(1 << 30, max_offset_only(&[1, 0], i32::MAX));
+    }
+
+    #[test]
+    fn separation_within_offset() {
+        assert_eq!(10, max_separation_within_offset(&[-10, -1, 1, 10], 5));
+        assert_eq!(10, max_separation_within_offset(&[-10, -1, 1, 10], 6));
+        assert_eq!(12, max_separation_within_offset(&[0, 0], 6));
+        assert_eq!(i32::MAX, max_separation_within_offset(&[0], 6));
+
+        for (positions, _) in random_inputs().into_iter().filter(|(p, _)| p.len() > 1) {
+            let separation = max_separation_within_offset(&positions, 15);
+
+            assert!(max_offset_only(&positions, separation) <= 15);
+            assert!(max_offset_only(&positions, separation + 1) > 15);
+        }
+    }
+
+    #[test]
+    fn reversed() {
+        for (positions, separation) in random_inputs() {
+            let permitted_positions = place_reversed(&positions, separation);
+
+            assert_eq!(
+                place(&positions, separation)
+                    .into_iter()
+                    .rev()
+                    .collect::<Vec<_>>(),
+                permitted_positions
+            );
+            assert_eq!(positions.len(), permitted_positions.capacity());
+        }
+    }
+
+    #[test]
+    fn by_category() {
+        assert_eq!(
+            vec![-5, -5, 5, 5],
+            place_by_category(&[0, 0, 1, 1], 10, &[1, 2, 1, 2])
+        );
+        assert_eq!(
+            vec![-10, 0, 0, 10],
+            place_by_category(&[0, 0, 0, 0], 10, &[7, 3, 7, 7])
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_by_category(&positions, separation, &vec![0; positions.len()])
+            );
+        }
+    }
+
+    #[test]
+    fn explanations() {
+        let explanations = explain(&[0, 10, 20, 30, 31], 10);
+
+        assert_eq!(
+            Explanation {
+                moved_by: 4,
+                cluster_index: 0,
+                cluster_member_count: 5
+            },
+            explanations[4]
+        );
+        assert!(explanations
+            .iter()
+            .all(|explanation| explanation.cluster_index == 0));
+
+        let explanations = explain(&[0, 0, 100], 10);
+
+        assert_eq!(
+            [-5, 5, 0],
+            *explanations
+                .iter()
+                .map(|explanation| explanation.moved_by)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, explanations[2].cluster_index);
+        assert_eq!(1, explanations[2].cluster_member_count);
+    }
+
+    #[test]
+    fn weighted_f64() {
+        assert_eq!(vec![-1, 9], place_weighted_f64(&[0, 0], 10, &[0.9, 0.1]));
+        assert_eq!(vec![-9, 1], place_weighted_f64(&[0, 0], 10, &[0.1, 0.9]));
+        assert_eq!(vec![0, 10], place_weighted_f64(&[0, 0], 10, &[1.0, 0.0]));
+        assert_eq!(vec![-5, 5], place_weighted_f64(&[0, 0], 10, &[0.0, 0.0]));
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place_full(
+                    &positions,
+                    separation,
+                    &vec![1; positions.len()],
+                    i32::MIN,
+                    i32::MAX
+                ),
+                place_weighted_f64(&positions, separation, &vec![0.5; positions.len()])
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn weighted_f64_negative() {
+        place_weighted_f64(&[0, 0], 10, &[1.0, -1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn weighted_f64_too_few_weights() {
+        place_weighted_f64(&[0, 0], 10, &[1.0]);
+    }
+
+    #[test]
+    fn incremental_push() {
+        for (positions, separation) in random_inputs() {
+            let mut placer = IncrementalPlacer::new(separation);
+
+            for (index, position) in positions.iter().enumerate() {
+                placer.push(*position);
+                assert_eq!(place(&positions[..=index], separation), placer.positions());
+            }
+
+            let mut positions = positions.clone();
+            positions.insert(0, -1000);
+            placer.push(-1000);
+            assert_eq!(place(&positions, separation), placer.positions());
+        }
+    }
+
+    #[test]
+    fn sized_baseline() {
+        assert_eq!(
+            vec![80],
+            place_sized_baseline(&[95], &[5], &[20], 0, 0, 100)
+        );
+        assert_eq!(
+            vec![95],
+            place_sized_baseline(&[95], &[20], &[5], 0, 0, 100)
+        );
+        assert_eq!(vec![10], place_sized_baseline(&[0], &[10], &[5], 0, 0, 100));
+
+        let baselines = place_sized_baseline(&[50, 50], &[10, 10], &[2, 2], 3, 0, 100);
+        assert_eq!(vec![42, 57], baselines);
+        assert_eq!(baselines[0] + 2 + 3, baselines[1] - 10);
+    }
+
+    #[test]
+    fn invariants() {
+        for (positions, separation) in random_inputs() {
+            let placed = place(&positions, separation);
+
+            assert_eq!(positions.len(), placed.len());
+            assert!(is_valid_placement(&placed, separation, None, None));
+
+            for (min, max) in [(-200, 200), (0, 50), (-20, -20), (100, 1000)] {
+                let placed = place_with_limits(&positions, separation, min, max);
+                let required = separation as i64 * (positions.len().max(1) - 1) as i64;
+
+                assert_eq!(positions.len(), placed.len());
+
+                if required <= i64::from(max) - i64::from(min) {
+                    assert!(is_valid_placement(
+                        &placed,
+                        separation,
+                        Some(min),
+                        Some(max)
+                    ));
+                } else {
+                    assert!(is_valid_placement(&placed, separation, None, Some(max)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invariants_at_extremes() {
+        let high = [i32::MAX - 40, i32::MAX - 39, i32::MAX - 38];
+        let low = [i32::MIN + 38, i32::MIN + 39, i32::MIN + 40];
+
+        assert!(is_valid_placement(&place(&high, 10), 10, None, None));
+        assert!(is_valid_placement(&place(&low, 10), 10, None, None));
+
+        assert!(is_valid_placement(
+            &place_with_limits(&[i32::MAX - 20; 3], 10, 0, i32::MAX - 15),
+            10,
+            Some(0),
+            Some(i32::MAX - 15)
+        ));
+        assert!(is_valid_placement(
+            &place_with_limits(&[i32::MIN + 20; 3], 10, i32::MIN + 15, 0),
+            10,
+            Some(i32::MIN + 15),
+            Some(0)
+        ));
+
+        assert!(is_valid_placement(
+            &place(&[i32::MIN, 0, i32::MAX], i32::MAX),
+            i32::MAX,
+            None,
+            None
+        ));
+    }
+
+    #[test]
+    fn inertia() {
+        let positions = [0, 5, 60, 61];
+        let previous = [-40, -30, 0, 10];
+
+        let distance = |inertia: f64| -> i32 {
+            place_with_inertia(&positions, 10, &previous, inertia)
+                .iter()
+                .zip(previous)
+                .map(|(placed, previous)| (placed - previous).abs())
+                .sum()
+        };
+
+        assert!(distance(0.8) < distance(0.5));
+        assert!(distance(0.5) < distance(0.2));
+
+        assert_eq!(
+            place(&positions, 10),
+            place_with_inertia(&positions, 10, &previous, 0.0)
+        );
+        assert_eq!(
+            previous.to_vec(),
+            place_with_inertia(&positions, 10, &previous, 1.0)
+        );
+    }
+
+    #[test]
+    fn boundaries() {
+        assert_eq!(
+            Vec::<usize>::new(),
+            cluster_boundaries(&[0, 10, 20, 30, 31], 10)
+        );
+        assert_eq!(vec![2], cluster_boundaries(&[0, 0, 100], 10));
+        assert_eq!(vec![1, 2], cluster_boundaries(&[0, 10, 20], 10));
+        assert_eq!(Vec::<usize>::new(), cluster_boundaries(&[], 10));
+    }
+
+    #[test]
+    fn overrides() {
+        assert_eq!(
+            vec![-20, 10, 20],
+            place_with_overrides(&[0, 0, 0], 10, &[(0, 30)])
+        );
+        assert_eq!(
+            vec![0, 85, 115],
+            place_with_overrides(&[0, 100, 101], 10, &[(1, 30)])
+        );
+        assert_eq!(
+            vec![-5, 5],
+            place_with_overrides(&[0, 0], 10, &[(1, 30), (5, 30)])
+        );
+        assert_eq!(
+            vec![-5, 5],
+            place_with_overrides(&[0, 0], 10, &[(usize::MAX, 30)])
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_with_overrides(&positions, separation, &[])
+            );
+        }
+    }
+
+    #[test]
+    fn repaired() {
+        let mut positions = [0, 10, 19, 30, 50, 70];
+        repair(&mut positions, 10);
+        assert_eq!([-1, 9, 19, 30, 50, 70], positions);
+
+        for (positions, separation) in random_inputs() {
+            let mut repaired = place(&positions, separation);
+            repair(&mut repaired, separation);
+            assert_eq!(place(&positions, separation), repaired);
+        }
+    }
+
+    #[test]
+    fn grid_deltas() {
+        assert_eq!(vec![-3, 4, 0], place_grid_deltas(&[-3, 14, 100], 10));
+
+        for (positions, separation) in random_inputs().into_iter().filter(|(_, s)| *s > 0) {
+            let placed = place(&positions, separation);
+            let deltas = place_grid_deltas(&positions, separation);
+
+            for (placed, delta) in placed.into_iter().zip(deltas) {
+                assert!(2 * delta.abs() <= separation);
+                assert_eq!(0, (placed - delta) % separation);
+            }
+        }
+    }
+
+    #[test]
+    fn limits_compress() {
+        assert_eq!(
+            vec![0, 5, 10],
+            place_with_limits_compress(&[0, 0, 0], 10, 0, 10)
+        );
+        assert_eq!(
+            vec![20, 30, 40],
+            place_with_limits_compress(&[30, 30, 30], 10, 0, 100)
+        );
+
+        for (positions, separation) in random_inputs() {
+            let placed = place_with_limits_compress(&positions, separation, -20, 20);
+            assert!(is_valid_placement(&placed, 0, Some(-20), Some(20)));
+        }
+    }
+
+    #[test]
+    fn merge_filter() {
+        let placed = place_with_merge_filter(&[0, 0, 10, 20], 10, |_, next_start| next_start < 10);
+        assert_eq!(vec![-5, 5, 10, 20], placed);
+        assert!(!are_separated(&placed, 10));
+
+        assert_eq!(
+            vec![0, 0, 0],
+            place_with_merge_filter(&[0, 0, 0], 10, |_, _| false)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_with_merge_filter(&positions, separation, |_, _| true)
+            );
+        }
     }
 
     #[test]
-    fn odd_separation() {
-        assert_eq!([-3, 2], *place(&[0, 0], 5));
-        assert_eq!([-5, 0, 5], *place(&[0, 0, 0], 5));
-        assert_eq!([-8, -3, 2, 7], *place(&[0, 0, 0, 0], 5));
+    fn bounds() {
+        let positions = [0, 0, 100, 101];
+        assert_eq!(Some((-5, 105)), free_bounds(&positions, 10));
+        assert_eq!(
+            place(&positions, 10),
+            place_with_limits(&positions, 10, -5, 105)
+        );
+        assert_ne!(
+            place(&positions, 10),
+            place_with_limits(&positions, 10, -4, 105)
+        );
+        assert_eq!(None, free_bounds(&[], 10));
+    }
+
+    #[test]
+    fn conserve_mean() {
+        let positions = [0, 0, 5, 5, 5];
+        let placed = place_conserve_mean(&positions, 5);
+        assert_eq!(vec![-8, -3, 2, 7, 12], place(&positions, 5));
+        assert_eq!(vec![-7, -2, 3, 8, 13], placed);
+        assert_eq!(positions.iter().sum::<i32>(), placed.iter().sum::<i32>());
+
+        assert_eq!(vec![-3, 2], place_conserve_mean(&[0, 0], 5));
+        assert_eq!(Vec::<i32>::new(), place_conserve_mean(&[], 5));
+
+        for (positions, separation) in random_inputs() {
+            let placed = place_conserve_mean(&positions, separation);
+            assert!(is_valid_placement(&placed, separation, None, None));
+            assert_eq!(
+                max_offset_only(&positions, separation),
+                placed
+                    .iter()
+                    .zip(&positions)
+                    .map(|(placed, preferred)| (placed - preferred).abs())
+                    .max()
+                    .unwrap_or(0)
+            );
+        }
+    }
+
+    #[test]
+    fn map() {
+        for (positions, separation) in random_inputs() {
+            let placed = place(&positions, separation);
+            let map = place_map(&positions, separation);
+
+            assert_eq!(placed.len(), map.len());
+
+            for (index, position) in placed.into_iter().enumerate() {
+                assert_eq!(Some(&position), map.get(&index));
+            }
+        }
+    }
+
+    #[test]
+    fn jittered() {
+        let positions = [0, 0, 100, 200, 300, 400];
+        let jittered = place_jittered(&positions, 10, -100, 500, 1);
+
+        assert_eq!(jittered, place_jittered(&positions, 10, -100, 500, 1));
+        assert!((2..10).any(|seed| jittered != place_jittered(&positions, 10, -100, 500, seed)));
+
+        for (positions, separation) in random_inputs() {
+            let limited = place_with_limits(&positions, separation, -300, 300);
+            let max_offset = |placed: &[i32]| {
+                placed
+                    .iter()
+                    .zip(&positions)
+                    .map(|(placed, preferred)| (placed - preferred).abs())
+                    .max()
+            };
+
+            for seed in 0..4 {
+                let jittered = place_jittered(&positions, separation, -300, 300, seed);
+
+                assert!(max_offset(&jittered) <= max_offset(&limited));
+                assert!(is_valid_placement(
+                    &jittered,
+                    separation,
+                    Some(-300),
+                    Some(300)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        let (placed, metrics) = place_with_metrics(&[0, 0, 0, 50, 100, 101], 10);
+        assert_eq!(vec![-10, 0, 10, 50, 95, 105], placed);
+        assert_eq!(
+            Metrics {
+                max_offset: 10,
+                total_displacement: 29,
+                cluster_count: 3,
+                max_cluster_size: 3
+            },
+            metrics
+        );
+
+        assert_eq!((vec![], Metrics::default()), place_with_metrics(&[], 10));
+
+        for (positions, separation) in random_inputs() {
+            let (placed, metrics) = place_with_metrics(&positions, separation);
+            let (_, stats) = place_with_stats(&positions, separation);
+
+            assert_eq!(place(&positions, separation), placed);
+            assert_eq!(
+                max_offset_only(&positions, separation),
+                metrics.max_offset as i32
+            );
+            assert_eq!(stats.cluster_count, metrics.cluster_count);
+            assert_eq!(stats.max_cluster_size, metrics.max_cluster_size);
+        }
+    }
+
+    #[test]
+    fn relative() {
+        assert_eq!(
+            place(&[0, 1, 3, 100], 10),
+            place_relative(&[0, 1, 3, 100], 0.1)
+        );
+        assert_eq!(place(&[0, 0, 200], 50), place_relative(&[0, 0, 200], 0.249));
+        assert_eq!(vec![5, 5, 5], place_relative(&[5, 5, 5], 0.5));
+        assert_eq!(Vec::<i32>::new(), place_relative(&[], 0.5));
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(
+            vec![-10, -10, -10, 100],
+            place_with_layout(&[0, 0, 0, 100], 10, |_| 0)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_with_layout(&positions, separation, |index| separation * index as i32)
+            );
+        }
+    }
+
+    #[test]
+    fn presorted() {
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place(&positions, separation),
+                place_presorted(&positions, separation)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn presorted_unsorted() {
+        place_presorted(&[10, 0], 10);
+    }
+
+    #[test]
+    fn overlapping() {
+        assert_eq!(vec![5, 0, -5], place_overlapping(&[10, 0, -10], 5));
+        assert_eq!(vec![9, 1, -4], place_overlapping(&[10, 0, -4], 8));
+        assert_eq!(vec![2, 1, 0], place_overlapping(&[2, 1, 0], 5));
+        assert_eq!(vec![0, 0, 0], place_overlapping(&[0, 0, 0], 0));
+
+        for (positions, _) in random_inputs() {
+            let descending: Vec<i32> = positions.into_iter().rev().collect();
+            let placed = place_overlapping(&descending, 5);
+
+            for pair in placed.windows(2) {
+                assert!(pair[1] >= pair[0] - 5);
+            }
+        }
+    }
+
+    #[test]
+    fn i16_positions() {
+        assert_eq!(
+            vec![i16::MAX - 15, i16::MAX - 5],
+            place_i16(&[i16::MAX - 10, i16::MAX - 10], 10)
+        );
+        assert_eq!(
+            vec![i16::MAX - 5, i16::MAX],
+            place_i16(&[i16::MAX, i16::MAX], 10)
+        );
+        assert_eq!(
+            vec![0, i16::MAX, i16::MAX],
+            place_i16(&[i16::MAX; 3], i16::MAX)
+        );
+
+        let placed = place_i16(&vec![i16::MAX; 32_000], i16::MAX);
+        assert_eq!(32_000, placed.len());
+        assert_eq!(i16::MIN, placed[0]);
+        assert_eq!(i16::MAX, placed[31_999]);
+        assert_eq!(i16::MAX / 2, placed[15_999]);
+
+        for (positions, separation) in random_inputs() {
+            let narrowed: Vec<i16> = positions.iter().map(|position| *position as i16).collect();
+            let placed: Vec<i16> = place(&positions, separation)
+                .into_iter()
+                .map(|position| position as i16)
+                .collect();
+
+            assert_eq!(placed, place_i16(&narrowed, separation as i16));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn i16_positions_too_many() {
+        place_i16(&vec![i16::MAX; 70_000], i16::MAX);
+    }
+
+    #[test]
+    fn trace() {
+        let (placed, trace) = place_with_trace(&[0, 10, 20, 30, 31], 10);
+        assert_eq!(place(&[0, 10, 20, 30, 31], 10), placed);
+        assert_eq!(
+            vec![
+                MergeEvent {
+                    first: (30, 30),
+                    second: (31, 31),
+                    merged: (25, 35)
+                },
+                MergeEvent {
+                    first: (20, 20),
+                    second: (25, 35),
+                    merged: (15, 35)
+                },
+                MergeEvent {
+                    first: (10, 10),
+                    second: (15, 35),
+                    merged: (5, 35)
+                },
+                MergeEvent {
+                    first: (0, 0),
+                    second: (5, 35),
+                    merged: (-5, 35)
+                },
+            ],
+            trace
+        );
+
+        for (positions, separation) in random_inputs() {
+            let (placed, trace) = place_with_trace(&positions, separation);
+            let (_, stats) = place_with_stats(&positions, separation);
+
+            assert_eq!(place(&positions, separation), placed);
+            assert_eq!(stats.merges, trace.len());
+        }
+    }
+
+    #[test]
+    fn limits_are_optimal() {
+        let feasible = |positions: &[i32], separation: i32, min: i32, max: i32, offset: i32| {
+            let mut previous: Option<i32> = None;
+
+            positions.iter().all(|position| {
+                let mut low = min.max(position - offset);
+
+                if let Some(previous) = previous {
+                    low = low.max(previous + separation);
+                }
+
+                previous = Some(low);
+                low <= max.min(position + offset)
+            })
+        };
+
+        for (positions, separation) in random_inputs() {
+            for (min, max) in [(-200, 200), (0, 50), (-100, 300), (-150, 0), (-30, 30)] {
+                if i64::from(separation) * (positions.len().max(1) as i64 - 1)
+                    > i64::from(max - min)
+                {
+                    continue;
+                }
+
+                let placed = place_with_limits(&positions, separation, min, max);
+                let max_offset = placed
+                    .iter()
+                    .zip(&positions)
+                    .map(|(placed, preferred)| (placed - preferred).abs())
+                    .max()
+                    .unwrap_or(0);
+
+                assert!(feasible(&positions, separation, min, max, max_offset));
+                assert!(
+                    max_offset == 0 || !feasible(&positions, separation, min, max, max_offset - 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn keep_at_least() {
+        assert_eq!(
+            vec![Some(-10), None, Some(0), Some(10)],
+            place_keep_at_least(&[0, 0, 0, 0], 10, 0, 10, 3, &[2, 1, 2, 2])
+        );
+        assert_eq!(
+            vec![None, Some(0), Some(10), None],
+            place_keep_at_least(&[0, 0, 0, 0], 10, 0, 10, 0, &[1, 2, 2, 1])
+        );
+        assert_eq!(
+            vec![Some(0), Some(10)],
+            place_keep_at_least(&[0, 0], 10, 0, 100, 0, &[0, 0])
+        );
+        assert_eq!(
+            vec![None, Some(5)],
+            place_keep_at_least(&[0, 5], 10, 5, 5, 1, &[0, 1])
+        );
+    }
+
+    #[test]
+    fn connectors() {
+        for (positions, separation) in random_inputs() {
+            let connectors = place_connectors(&positions, separation);
+
+            assert_eq!(positions.len(), connectors.len());
+
+            for ((connector, point), placed) in connectors
+                .into_iter()
+                .zip(&positions)
+                .zip(place(&positions, separation))
+            {
+                assert_eq!(*point, connector.point);
+                assert_eq!(placed, connector.label_position);
+                assert_eq!(connector.label_position - connector.point, connector.offset);
+            }
+        }
+    }
+
+    #[test]
+    fn limits_split() {
+        assert_eq!(
+            vec![-15, -5, 5, 15],
+            place_with_limits_split(&[0, 0, 0, 0], 10, -5, 5, 0.5)
+        );
+        assert_eq!(
+            vec![-5, 5, 15, 25],
+            place_with_limits_split(&[0, 0, 0, 0], 10, -5, 5, 1.0)
+        );
+        assert_eq!(
+            vec![-20, -10, 0, 10],
+            place_with_limits_split(&[-10, -1, 1, 10], 10, -10, 10, 0.0)
+        );
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place_with_limits(&positions, separation, -20, 20),
+                place_with_limits_split(&positions, separation, -20, 20, 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn limits_status() {
+        assert_eq!(
+            vec![
+                (-15, LimitStatus::Overflowed),
+                (-5, LimitStatus::Placed),
+                (5, LimitStatus::Placed),
+                (15, LimitStatus::Clamped)
+            ],
+            place_with_limits_status(&[5, 5, 5, 5], 10, -10, 15)
+        );
+        assert_eq!(
+            vec![(0, LimitStatus::Placed), (10, LimitStatus::Placed)],
+            place_with_limits_status(&[0, 10], 10, 0, 10)
+        );
+    }
+
+    #[test]
+    fn sized_with_zero_heights() {
+        let unlimited = |centers: &[i32], heights: &[i32]| {
+            place_sized_with_limits(centers, heights, 0, i32::MIN / 2, i32::MAX / 2)
+        };
+
+        assert_eq!([-10, 0, 10], *unlimited(&[0, 0, 0], &[20, 0, 20]));
+        assert_eq!([0, 0, 0], *unlimited(&[0, 0, 0], &[0, 0, 0]));
+        assert_eq!(
+            [-10, -10, -5, 5, 10],
+            *unlimited(&[0, 0, 0, 0, 0], &[0, 0, 10, 10, 0])
+        );
+        assert_eq!(
+            [-5, -5, 5],
+            *place_with_overrides(&[0, 0, 0], 10, &[(0, 0)])
+        );
+    }
+
+    #[test]
+    fn limits_slack() {
+        assert_eq!(
+            (vec![0, 10, 100], 0, 20),
+            place_with_limits_slack(&[-5, 0, 100], 10, 0, 120)
+        );
+        assert_eq!(
+            (vec![-20, -10, 0, 10], -10, 0),
+            place_with_limits_slack(&[-10, -1, 1, 10], 10, -10, 10)
+        );
+        assert_eq!((vec![], 20, 20), place_with_limits_slack(&[], 10, 0, 20));
+    }
+
+    #[test]
+    fn layout_limits() {
+        for (positions, separation) in random_inputs() {
+            let mut layout = Layout::new(&positions, separation, -300, 300);
+            assert_eq!(
+                place_with_limits(&positions, separation, -300, 300),
+                layout.positions()
+            );
+
+            for min in [-250, -100, -20, 0, 50, 150, 300] {
+                layout.set_min(min);
+                assert_eq!(
+                    place_with_limits(&positions, separation, min, 300),
+                    layout.positions()
+                );
+            }
+
+            layout.set_min(-100);
+
+            for max in [250, 100, 20, 0, -50, -150] {
+                layout.set_max(max);
+                assert_eq!(
+                    place_with_limits(&positions, separation, -100, max),
+                    layout.positions()
+                );
+                assert_eq!((-100, max), (layout.min(), layout.max()));
+            }
+        }
+    }
+
+    #[test]
+    fn dual() {
+        assert_eq!(
+            (vec![0, 10, 100], vec![80, 90, 100]),
+            place_dual(&[0, 0, 100], &[100, 100, 100], 10, 0, 100)
+        );
+        assert_eq!(
+            (vec![], vec![-10, 0]),
+            place_dual(&[], &[0, 0], 10, -100, 0)
+        );
+    }
+
+    #[test]
+    fn unsorted_with_permutation() {
+        let positions = [20, 0, 20, 0];
+        let colours = ["red", "green", "blue", "yellow"];
+
+        let (placed, permutation) = place_unsorted_with_permutation(&positions, 10);
+        assert_eq!(vec![15, -5, 25, 5], placed);
+
+        let sorted_colours: Vec<&str> = permutation.iter().map(|&index| colours[index]).collect();
+        assert_eq!(vec!["green", "yellow", "red", "blue"], sorted_colours);
+
+        let sorted_placed: Vec<i32> = permutation.iter().map(|&index| placed[index]).collect();
+        assert_eq!(place(&[0, 0, 20, 20], 10), sorted_placed);
+    }
+
+    #[test]
+    fn clear_of() {
+        assert_eq!(
+            vec![0, 15, 40],
+            place_clear_of(&[0, 18, 40], 10, &[20, 35], 5)
+        );
+        assert_eq!(vec![0, 25, 40], place_clear_of(&[0, 22, 40], 10, &[20], 5));
+        assert_eq!(
+            vec![-5, 5, 25],
+            place_clear_of(&[0, 0, 30], 10, &[20, 30, 40], 5)
+        );
+        assert_eq!(
+            vec![-5, 5, 46],
+            place_clear_of(&[0, 0, 31], 10, &[20, 30, 40], 6)
+        );
+        assert_eq!(vec![0, 0], place_clear_of(&[0, 0], 0, &[0], 0));
+
+        for (positions, separation) in random_inputs() {
+            assert_eq!(
+                place_avoiding_zone(&positions, separation, -24, 15),
+                place_clear_of(&positions, separation, &[-5], 20)
+            );
+
+            if positions.len() as i32 * separation > 60 {
+                continue;
+            }
+
+            let placed = place_clear_of(&positions, separation, &[-100, 0, 100], 10);
+            assert!(is_valid_placement(&placed, separation, None, None));
+
+            for position in placed {
+                assert!([-100, 0, 100]
+                    .iter()
+                    .all(|marker: &i32| (position - marker).abs() >= 10));
+            }
+        }
+    }
+
+    #[test]
+    fn hysteresis() {
+        let positions = [0, 0, 50, 100];
+        let previous = place(&positions, 10);
+
+        assert_eq!(
+            previous,
+            place_with_hysteresis(&[1, 1, 50, 100], 10, &previous, 1)
+        );
+        assert_eq!(
+            vec![-5, 5, 50, 103],
+            place_with_hysteresis(&[1, 1, 50, 103], 10, &previous, 1)
+        );
+        assert_eq!(
+            place(&[3, 3, 50, 100], 10),
+            place_with_hysteresis(&[3, 3, 50, 100], 10, &previous, 2)
+        );
+
+        for (positions, separation) in random_inputs() {
+            let previous: Vec<i32> = positions.iter().map(|position| position + 3).collect();
+            let placed = place_with_hysteresis(&positions, separation, &previous, 5);
+            assert!(is_valid_placement(&placed, separation, None, None));
+        }
+    }
+
+    #[test]
+    fn moved_mask() {
+        for (positions, separation) in random_inputs() {
+            let (placed, mask) = place_with_moved_mask(&positions, separation);
+            let (_, offsets) = place_positions_and_offsets(&positions, separation);
+
+            assert_eq!(place(&positions, separation), placed);
+            assert_eq!(positions.len().div_ceil(64), mask.len());
+
+            for (index, offset) in offsets.into_iter().enumerate() {
+                assert_eq!(offset != 0, mask[index / 64] >> (index % 64) & 1 == 1);
+            }
+        }
+
+        let mut positions = vec![0; 128];
+        positions.extend([10_000, 10_000, 20_000]);
+        let (_, mask) = place_with_moved_mask(&positions, 10);
+        assert_eq!(vec![u64::MAX, u64::MAX, 0b011], mask);
+    }
+
+    #[test]
+    fn thinned() {
+        let positions: Vec<i32> = (0..10).collect();
+        let kept = place_thinned(&positions, 2, 0.6);
+        assert_eq!(vec![(0, 0), (2, 2), (4, 4), (6, 6), (8, 8)], kept);
+
+        assert_eq!(
+            vec![(0, 0), (1, 1), (2, 2)],
+            place_thinned(&[0, 1, 2], 1, 10.0)
+        );
+        assert_eq!(vec![(0, 5)], place_thinned(&[5, 5, 5], 10, 1.0));
+        assert_eq!(Vec::<(usize, i32)>::new(), place_thinned(&[], 10, 1.0));
+        assert_eq!(vec![(0, -5), (3, 5)], place_thinned(&[0, 0, 1, 1], 10, 1.5));
+    }
+
+    #[test]
+    fn sized_from_iter() {
+        assert_eq!(vec![-13, 13], place_sized_from_iter([(0, 20), (0, 20)], 6));
+
+        for (positions, separation) in random_inputs() {
+            let heights: Vec<i32> = positions
+                .iter()
+                .map(|position| position.rem_euclid(separation.max(1)) * 2)
+                .collect();
+
+            assert_eq!(
+                place_sized_with_limits(&positions, &heights, 3, i32::MIN / 2, i32::MAX / 2),
+                place_sized_from_iter(positions.iter().copied().zip(heights.iter().copied()), 3)
+            );
+        }
+    }
+
+    #[test]
+    fn lexicographic() {
+        let offsets = |positions: &[i32], placed: &[i32]| {
+            placed
+                .iter()
+                .zip(positions)
+                .map(|(placed, preferred)| (placed - preferred).abs())
+                .fold((0, 0), |(max_offset, total), offset| {
+                    (max(max_offset, offset), total + offset)
+                })
+        };
+
+        let positions = [0, 1, 1, 7];
+        assert_eq!(vec![-3, 0, 3, 7], place(&positions, 3));
+        assert_eq!(vec![-2, 1, 4, 7], place_lexicographic(&positions, 3));
+        assert_eq!((3, 6), offsets(&positions, &place(&positions, 3)));
+        assert_eq!(
+            (3, 5),
+            offsets(&positions, &place_lexicographic(&positions, 3))
+        );
+
+        for (positions, separation) in random_inputs() {
+            let placed = place_lexicographic(&positions, separation);
+            let (max_offset, _) = offsets(&positions, &placed);
+
+            assert!(is_valid_placement(&placed, separation, None, None));
+            assert_eq!(max_offset_only(&positions, separation), max_offset);
+        }
+    }
+
+    #[test]
+    fn compress_single_label() {
+        assert_eq!(
+            vec![5],
+            place_with_overflow(&[0], 10, 5, 5, Overflow::Compress)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn compress_invalid_limits() {
+        place_with_overflow(&[0], 10, 10, 0, Overflow::Compress);
     }
 }